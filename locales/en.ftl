@@ -0,0 +1,293 @@
+# English messages, every other catalog translates all of them
+
+## Main window
+
+announcement-spinning = Spinning
+announcement-winners = { $count ->
+    [one] Winner
+   *[other] Winners
+} : { $names }
+chance-of-winning = Chance of winning : { $percent } %
+back-button = ⬅ Back
+add-choice-hint = Add a choice
+max-choices-reached = Max amount of choices reached : { $max }
+add-button = Add
+generate-choices = Generate choices
+verify-fairness = Verify fairness
+spin-the-wheel = Spin the Wheel !
+clear-the-wheel = Clear the wheel
+drawer-toggle = ☰ Choices
+drawer-toggle-tooltip = Show the choices and the settings under the wheel
+presenter-mode = Presenter mode
+presenter-mode-tooltip = Show the wheel alone in another window
+section-saved-wheels = Saved wheels
+section-results = Results
+section-fair-rotation = Fair rotation
+section-spin-settings = Spin settings
+section-sound = Sound
+section-keyboard-shortcuts = Keyboard shortcuts
+section-celebration = Celebration
+section-spin-physics = Spin physics
+section-theme = Theme
+section-language = Language
+language-system = System
+
+## Sound
+
+sound-mute = Mute
+sound-volume = Volume
+sound-tick-modulation = Ticks follow the speed with their :
+sound-pitch = Pitch
+sound-fanfare = Fanfare for the winner
+sound-tick-file = Tick sound file (built-in if empty)
+sound-play-tick = Play the tick
+sound-fanfare-file = Fanfare sound file (built-in if empty)
+sound-play-fanfare = Play the fanfare
+sound-unavailable = This build has no sound, build it with the "audio" feature
+sound-unavailable-web = Sound is not available in the browser
+sound-read-error = Can't read "{ $path }" : { $error }
+sound-play-error = Can't play "{ $path }" : { $error }
+
+## Celebration
+
+celebration-reduce-motion = Reduce motion
+celebration-reduce-motion-tooltip = The winner is highlighted without any animation
+celebration-confetti = Confetti
+
+## Choice list
+
+edit-choice = Edit this choice
+rename-choice-hint = Rename the choice
+description-label = Shown when this choice wins:
+description-hint = Address, link or instructions. **bold**, *italic*, [links](https://example.com) and "- " lists work.
+description-copy = 📋 Copy
+opens-wheel-label = Opens the wheel :
+no-sub-wheel = None
+cancel-button = Cancel
+confirm-button = Confirm
+shuffle-button = 🔀 Shuffle
+sort-alphabetical-button = Sort A–Z
+sort-weight-button = Sort by weight
+enable-all-button = Enable all
+undo-button = ⟲ Undo
+disable-choice = Disable this choice
+enable-choice = Enable this choice
+opens-wheel = Opens the wheel "{ $name }"
+weight-label = Weight :
+increase-weight-of = Increase the weight of { $name }
+decrease-weight-of = Decrease the weight of { $name }
+chance-next-spin = Chance of winning the next spin
+edit-choice-of = Edit { $name }
+remove-choice = Remove this choice
+remove-choice-of = Remove { $name }
+
+## Fair rotation
+
+fairness-enabled = Lower the weight of recent winners
+fairness-decay = Weight kept after a win
+fairness-recovery = Recovered per
+fairness-spin = spin
+fairness-day = day
+fairness-reset = Reset the weights
+
+## Generator
+
+generator-numbers = Numbers
+generator-letters = Letters
+generator-dice = Dice
+generator-yes-no = Yes / No
+generator-days = Days
+generator-weights = Weights :
+generator-uniform = Uniform
+generator-linear = Linear
+generator-bell-curve = Bell curve
+generator-replace = Replace the current choices
+generator-preview = { $count ->
+    [one] { $count } choice
+   *[other] { $count } choices
+} : { $preview }
+generator-overflow = { $room ->
+    [one] Only the first choice fits on the wheel
+   *[other] Only the first { $room } choices fit on the wheel
+}
+generator-raised-weights = The least likely sums get the smallest weight, the wheel gives them better odds than real dice
+generator-generate = Generate
+generator-from = From
+generator-to = to
+generator-step = step
+generator-dice-odds = Each sum is weighted by its odds
+generator-add-maybe = Add "Maybe"
+generator-weekdays = Weekdays only
+generator-yes = Yes
+generator-no = No
+generator-maybe = Maybe
+day-monday = Monday
+day-tuesday = Tuesday
+day-wednesday = Wednesday
+day-thursday = Thursday
+day-friday = Friday
+day-saturday = Saturday
+day-sunday = Sunday
+
+## Keyboard shortcuts
+
+action-spin = Spin the wheel
+action-focus-add = Type a new choice
+action-add-choice = Add the typed choice
+action-previous-choice = Select the previous choice
+action-next-choice = Select the next choice
+action-increase-weight = Increase the weight
+action-decrease-weight = Decrease the weight
+action-rename = Edit the selected choice
+action-remove = Remove the selected choice
+action-close-dialog = Close the dialog
+action-show-shortcuts = Show the shortcuts
+keymap-press-key = Press a key…
+keymap-record-tooltip = Click, then press the new shortcut
+keymap-reset = Reset to defaults
+keymap-cheat-sheet = Show the cheat sheet
+
+## Saved wheels and results
+
+library-name-hint = Name of this wheel
+library-save = Save
+library-open = Open
+library-delete = Delete { $name }
+library-confirm-title = Replace a wheel
+library-overwrite = A saved wheel is already named "{ $name }". Replace it with this wheel ?
+library-open-confirm = Open "{ $name }" ? The current wheel will be replaced, save it first to keep it.
+results-empty = No spins yet
+results-clear = Clear the results
+elapsed-now = now
+elapsed-minutes = { $count } min ago
+elapsed-hours = { $count } h ago
+elapsed-days = { $count ->
+    [one] { $count } day ago
+   *[other] { $count } days ago
+}
+
+## Choice pictures
+
+icon-label = Picture:
+icon-none = None
+icon-remove = Remove the picture
+icon-file-hint = PNG, JPEG or SVG file
+icon-file-unavailable-web = Pictures can't be loaded from files in the browser, use an emoji
+icon-load = Load
+icon-emoji-hint = Emoji
+icon-use-emoji = Use this emoji
+icon-show-label = Show the label next to the picture
+icon-unsupported-format = "{ $path }" is not a PNG, JPEG or SVG file
+icon-read-error = Can't read "{ $path }" : { $error }
+icon-too-large = "{ $path }" is larger than { $max } KB
+icon-unknown-emoji = "{ $emoji }" is not a single emoji
+
+## Tags
+
+tags-label = Tags:
+tags-hint = New tag
+tags-add = Add
+tags-remove = Remove this tag
+tags-filter = Spin only:
+tags-match-any = any of these tags
+tags-match-all = all of these tags
+tags-clear = Show all
+
+## Spin physics
+
+physics-duration = Duration :
+physics-to = to
+physics-min-rotations = Minimum full rotations :
+physics-direction = Direction :
+physics-curve = Slowing down :
+physics-bounce = Bounce
+physics-summary = Spins last { $min } to { $max } s and do { $min_turns } to { $max_turns } full turns
+physics-clockwise = Clockwise
+physics-counter-clockwise = Counter-clockwise
+physics-random = Random
+physics-exponential = Exponential
+physics-linear = Linear
+physics-ease-out = Ease-out cubic
+
+## Presenter
+
+presenter-title = CargoSpin presenter
+presenter-window = Presenter
+presenter-hint = Click the wheel to spin it, F11 for fullscreen
+
+## Wheel
+
+winner-close = Close
+winner-remove = { $count ->
+    [one] Remove the winner
+   *[other] Remove the winners
+}
+winner-spin-sub-wheel = Spin "{ $name }"
+wheel-empty = Add options to spin the wheel !
+wheel-all-disabled = Enable options to spin the wheel !
+wheel-no-match = No option matches the tag filter !
+wheel-name = Wheel
+wheel-segment = { $name }, { $percent } %
+winner-first = Pick the winner before spinning
+winner-first-tooltip = The winner is drawn from the weights and the wheel is animated to land on it, so the odds match the weights exactly
+pointers-label = Pointers (degrees, clockwise from the right) :
+pointer-remove = Remove the pointer { $number }
+pointer-add = Add a pointer
+pointers-spread = Spread evenly
+duplicates-label = When pointers land on the same choice :
+duplicates-allow = keep it twice
+duplicates-next-choice = use the next choice
+
+## Fairness check
+
+cli-usage = Usage: cargo_spin simulate [--spins N] [--winner-first] LABEL[:WEIGHT]...
+cli-invalid-spins = --spins expects a positive number
+cli-invalid-weight = Invalid weight for "{ $label }"
+simulation-description = Spins the current wheel many times without drawing it and compares the results with the odds given by the weights.
+simulation-spins = Spins :
+simulation-run = Run
+report-choice = Choice
+report-expected = Expected
+report-observed = Observed
+report-wins = Wins
+report-degrees = { $degrees ->
+    [one] { $degrees } degree of freedom
+   *[other] { $degrees } degrees of freedom
+}
+report-statistic = χ² = { $chi_square } with { report-degrees }, p-value = { $p_value }
+report-summary = { $spins ->
+    [one] { $spins } spin
+   *[other] { $spins } spins
+}, chi-square = { $chi_square } with { report-degrees }, p-value = { $p_value }
+report-fair = The results are consistent with the weights.
+report-unfair = The results differ significantly from the weights.
+
+## Theme
+
+theme-system = System
+theme-light = Light
+theme-dark = Dark
+theme-high-contrast = High contrast
+theme-custom = Custom
+theme-edit = Edit the custom theme
+theme-editor = Theme editor
+theme-start-from = Start from :
+theme-name = Name
+theme-widgets = Widgets
+theme-background = Background
+theme-segments = Segments
+theme-add-color = Add a color
+theme-remove-color = Remove this color
+theme-remove-color-hint = Right-click a color to remove it
+theme-pointer = Pointer
+theme-label-color = Label color
+theme-label-font = Label font
+theme-font-proportional = Proportional
+theme-font-monospace = Monospace
+theme-file-hint = Theme file
+theme-file-unavailable-web = Theme files can't be used in the browser
+theme-export = Export
+theme-import = Import
+theme-empty-palette = The theme has no segment colors
+theme-read-error = Can't read "{ $path }" : { $error }
+theme-write-error = Can't write "{ $path }" : { $error }
@@ -0,0 +1,293 @@
+# Messages en français
+
+## Main window
+
+announcement-spinning = La roue tourne
+announcement-winners = { $count ->
+    [one] Gagnant
+   *[other] Gagnants
+} : { $names }
+chance-of-winning = Chance de gagner : { $percent } %
+back-button = ⬅ Retour
+add-choice-hint = Ajouter un choix
+max-choices-reached = Nombre maximum de choix atteint : { $max }
+add-button = Ajouter
+generate-choices = Générer des choix
+verify-fairness = Vérifier l'équité
+spin-the-wheel = Tourner la roue !
+clear-the-wheel = Vider la roue
+drawer-toggle = ☰ Choix
+drawer-toggle-tooltip = Afficher les choix et les réglages sous la roue
+presenter-mode = Mode présentation
+presenter-mode-tooltip = Afficher la roue seule dans une autre fenêtre
+section-saved-wheels = Roues enregistrées
+section-results = Résultats
+section-fair-rotation = Rotation équitable
+section-spin-settings = Réglages du tirage
+section-sound = Son
+section-keyboard-shortcuts = Raccourcis clavier
+section-celebration = Célébration
+section-spin-physics = Physique de la roue
+section-theme = Thème
+section-language = Langue
+language-system = Système
+
+## Sound
+
+sound-mute = Couper le son
+sound-volume = Volume
+sound-tick-modulation = Les clics suivent la vitesse par leur :
+sound-pitch = Hauteur
+sound-fanfare = Fanfare pour le gagnant
+sound-tick-file = Fichier du clic (intégré si vide)
+sound-play-tick = Écouter le clic
+sound-fanfare-file = Fichier de la fanfare (intégrée si vide)
+sound-play-fanfare = Écouter la fanfare
+sound-unavailable = Cette version n'a pas de son, compilez-la avec la fonctionnalité « audio »
+sound-unavailable-web = Le son n'est pas disponible dans le navigateur
+sound-read-error = Impossible de lire « { $path } » : { $error }
+sound-play-error = Impossible de jouer « { $path } » : { $error }
+
+## Celebration
+
+celebration-reduce-motion = Réduire les animations
+celebration-reduce-motion-tooltip = Le gagnant est mis en avant sans aucune animation
+celebration-confetti = Confettis
+
+## Choice list
+
+edit-choice = Modifier ce choix
+rename-choice-hint = Renommer le choix
+description-label = Affiché quand ce choix gagne :
+description-hint = Adresse, lien ou instructions. **gras**, *italique*, [liens](https://example.com) et listes « - » acceptés.
+description-copy = 📋 Copier
+opens-wheel-label = Ouvre la roue :
+no-sub-wheel = Aucune
+cancel-button = Annuler
+confirm-button = Valider
+shuffle-button = 🔀 Mélanger
+sort-alphabetical-button = Trier de A à Z
+sort-weight-button = Trier par poids
+enable-all-button = Tout activer
+undo-button = ⟲ Annuler
+disable-choice = Désactiver ce choix
+enable-choice = Activer ce choix
+opens-wheel = Ouvre la roue « { $name } »
+weight-label = Poids :
+increase-weight-of = Augmenter le poids de { $name }
+decrease-weight-of = Diminuer le poids de { $name }
+chance-next-spin = Chance de gagner le prochain tirage
+edit-choice-of = Modifier { $name }
+remove-choice = Supprimer ce choix
+remove-choice-of = Supprimer { $name }
+
+## Fair rotation
+
+fairness-enabled = Réduire le poids des derniers gagnants
+fairness-decay = Poids conservé après une victoire
+fairness-recovery = Récupéré par
+fairness-spin = tirage
+fairness-day = jour
+fairness-reset = Réinitialiser les poids
+
+## Generator
+
+generator-numbers = Nombres
+generator-letters = Lettres
+generator-dice = Dés
+generator-yes-no = Oui / Non
+generator-days = Jours
+generator-weights = Poids :
+generator-uniform = Uniformes
+generator-linear = Linéaires
+generator-bell-curve = En cloche
+generator-replace = Remplacer les choix actuels
+generator-preview = { $count ->
+    [one] { $count } choix
+   *[other] { $count } choix
+} : { $preview }
+generator-overflow = { $room ->
+    [one] Seul le premier choix tient sur la roue
+   *[other] Seuls les { $room } premiers choix tiennent sur la roue
+}
+generator-raised-weights = Les sommes les moins probables reçoivent le plus petit poids, la roue leur donne plus de chances que de vrais dés
+generator-generate = Générer
+generator-from = De
+generator-to = à
+generator-step = pas
+generator-dice-odds = Chaque somme est pondérée par sa probabilité
+generator-add-maybe = Ajouter « Peut-être »
+generator-weekdays = Jours de semaine seulement
+generator-yes = Oui
+generator-no = Non
+generator-maybe = Peut-être
+day-monday = Lundi
+day-tuesday = Mardi
+day-wednesday = Mercredi
+day-thursday = Jeudi
+day-friday = Vendredi
+day-saturday = Samedi
+day-sunday = Dimanche
+
+## Keyboard shortcuts
+
+action-spin = Tourner la roue
+action-focus-add = Saisir un nouveau choix
+action-add-choice = Ajouter le choix saisi
+action-previous-choice = Sélectionner le choix précédent
+action-next-choice = Sélectionner le choix suivant
+action-increase-weight = Augmenter le poids
+action-decrease-weight = Diminuer le poids
+action-rename = Modifier le choix sélectionné
+action-remove = Supprimer le choix sélectionné
+action-close-dialog = Fermer la fenêtre
+action-show-shortcuts = Afficher les raccourcis
+keymap-press-key = Appuyez sur une touche…
+keymap-record-tooltip = Cliquez, puis appuyez sur le nouveau raccourci
+keymap-reset = Rétablir les raccourcis par défaut
+keymap-cheat-sheet = Afficher l'aide-mémoire
+
+## Saved wheels and results
+
+library-name-hint = Nom de cette roue
+library-save = Enregistrer
+library-open = Ouvrir
+library-delete = Supprimer { $name }
+library-confirm-title = Remplacer une roue
+library-overwrite = Une roue enregistrée s'appelle déjà « { $name } ». La remplacer par cette roue ?
+library-open-confirm = Ouvrir « { $name } » ? La roue actuelle sera remplacée, enregistrez-la d'abord pour la garder.
+results-empty = Aucun tirage pour l'instant
+results-clear = Effacer les résultats
+elapsed-now = à l'instant
+elapsed-minutes = il y a { $count } min
+elapsed-hours = il y a { $count } h
+elapsed-days = { $count ->
+    [one] il y a { $count } jour
+   *[other] il y a { $count } jours
+}
+
+## Choice pictures
+
+icon-label = Image :
+icon-none = Aucune
+icon-remove = Retirer l'image
+icon-file-hint = Fichier PNG, JPEG ou SVG
+icon-file-unavailable-web = Les images ne peuvent pas être chargées depuis un fichier dans le navigateur, utilisez un emoji
+icon-load = Charger
+icon-emoji-hint = Émoji
+icon-use-emoji = Utiliser cet émoji
+icon-show-label = Afficher le libellé à côté de l'image
+icon-unsupported-format = « { $path } » n'est pas un fichier PNG, JPEG ou SVG
+icon-read-error = Impossible de lire « { $path } » : { $error }
+icon-too-large = « { $path } » dépasse { $max } Ko
+icon-unknown-emoji = « { $emoji } » n'est pas un émoji unique
+
+## Tags
+
+tags-label = Tags :
+tags-hint = Nouveau tag
+tags-add = Ajouter
+tags-remove = Retirer ce tag
+tags-filter = Tirer seulement :
+tags-match-any = l'un de ces tags
+tags-match-all = tous ces tags
+tags-clear = Tout afficher
+
+## Spin physics
+
+physics-duration = Durée :
+physics-to = à
+physics-min-rotations = Tours complets minimum :
+physics-direction = Sens :
+physics-curve = Ralentissement :
+physics-bounce = Rebond
+physics-summary = Les tirages durent de { $min } à { $max } s et font de { $min_turns } à { $max_turns } tours complets
+physics-clockwise = Horaire
+physics-counter-clockwise = Antihoraire
+physics-random = Aléatoire
+physics-exponential = Exponentiel
+physics-linear = Linéaire
+physics-ease-out = Cubique
+
+## Presenter
+
+presenter-title = CargoSpin présentation
+presenter-window = Présentation
+presenter-hint = Cliquez sur la roue pour la faire tourner, F11 pour le plein écran
+
+## Wheel
+
+winner-close = Fermer
+winner-remove = { $count ->
+    [one] Retirer le gagnant
+   *[other] Retirer les gagnants
+}
+winner-spin-sub-wheel = Tourner « { $name } »
+wheel-empty = Ajoutez des choix pour tourner la roue !
+wheel-all-disabled = Activez des choix pour tourner la roue !
+wheel-no-match = Aucun choix ne correspond au filtre de tags !
+wheel-name = Roue
+wheel-segment = { $name }, { $percent } %
+winner-first = Choisir le gagnant avant de tourner
+winner-first-tooltip = Le gagnant est tiré selon les poids puis la roue est animée pour s'arrêter dessus, les chances correspondent donc exactement aux poids
+pointers-label = Pointeurs (degrés, dans le sens horaire depuis la droite) :
+pointer-remove = Supprimer le pointeur { $number }
+pointer-add = Ajouter un pointeur
+pointers-spread = Répartir régulièrement
+duplicates-label = Quand des pointeurs tombent sur le même choix :
+duplicates-allow = le garder deux fois
+duplicates-next-choice = prendre le choix suivant
+
+## Fairness check
+
+cli-usage = Utilisation : cargo_spin simulate [--spins N] [--winner-first] CHOIX[:POIDS]...
+cli-invalid-spins = --spins attend un nombre positif
+cli-invalid-weight = Poids invalide pour « { $label } »
+simulation-description = Fait tourner la roue actuelle de nombreuses fois sans l'afficher et compare les résultats avec les chances données par les poids.
+simulation-spins = Tirages :
+simulation-run = Lancer
+report-choice = Choix
+report-expected = Attendu
+report-observed = Observé
+report-wins = Victoires
+report-degrees = { $degrees ->
+    [one] { $degrees } degré de liberté
+   *[other] { $degrees } degrés de liberté
+}
+report-statistic = χ² = { $chi_square } avec { report-degrees }, valeur p = { $p_value }
+report-summary = { $spins ->
+    [one] { $spins } tirage
+   *[other] { $spins } tirages
+}, khi carré = { $chi_square } avec { report-degrees }, valeur p = { $p_value }
+report-fair = Les résultats sont cohérents avec les poids.
+report-unfair = Les résultats diffèrent significativement des poids.
+
+## Theme
+
+theme-system = Système
+theme-light = Clair
+theme-dark = Sombre
+theme-high-contrast = Contraste élevé
+theme-custom = Personnalisé
+theme-edit = Modifier le thème personnalisé
+theme-editor = Éditeur de thème
+theme-start-from = Partir de :
+theme-name = Nom
+theme-widgets = Widgets
+theme-background = Fond
+theme-segments = Segments
+theme-add-color = Ajouter une couleur
+theme-remove-color = Supprimer cette couleur
+theme-remove-color-hint = Clic droit sur une couleur pour la supprimer
+theme-pointer = Pointeur
+theme-label-color = Couleur des libellés
+theme-label-font = Police des libellés
+theme-font-proportional = Proportionnelle
+theme-font-monospace = Chasse fixe
+theme-file-hint = Fichier du thème
+theme-file-unavailable-web = Les fichiers de thème ne sont pas disponibles dans le navigateur
+theme-export = Exporter
+theme-import = Importer
+theme-empty-palette = Le thème n'a aucune couleur de segment
+theme-read-error = Impossible de lire « { $path } » : { $error }
+theme-write-error = Impossible d'écrire « { $path } » : { $error }
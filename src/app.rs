@@ -1,39 +1,130 @@
+mod accessibility;
+mod audio;
+mod celebration;
 mod choice_list;
 mod constants;
+mod description;
+mod fairness;
+mod generator;
+mod i18n;
+mod icon;
+mod keymap;
+mod layout;
+mod library;
+mod physics;
+mod presenter;
+mod simulator;
+mod tags;
+mod theme;
 mod wheel;
 
+use audio::{Audio, AudioSettings};
+use celebration::{Celebration, CelebrationSettings};
 use choice_list::ChoiceList;
-use eframe::{
-    egui::{self, Color32, FontId, Pos2, Stroke},
-    epaint::PathShape,
-};
-use egui::Key;
-use wheel::Wheel;
+use eframe::egui;
+use egui_modal::Modal;
+use fairness::{FairRotation, Fairness};
+use generator::Generator;
+use i18n::{t, Language};
+use icon::Icon;
+use keymap::{Action, Keymap};
+use layout::LayoutMode;
+use library::{Crumb, ResultHistory, WheelLibrary};
+use presenter::Presenter;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+pub use simulator::run_simulation_cli;
+use simulator::FairnessDialog;
+use tags::TagFilter;
+use theme::{Appearance, ThemeSettings};
+use wheel::{SpinSettings, Wheel, WinnerEvent};
+
+// Text box where new choices are typed
+const ADD_INPUT_ID: &str = "add_choice";
 
 pub struct App {
     wheel: Wheel,
     input_text: String,
     wheel_choices: WheelChoices,
     choices_ui: ChoiceList,
+    fairness_dialog: FairnessDialog,
+    generator: Generator,
+    library: WheelLibrary,
+    results: ResultHistory,
+    // Parent wheels while spinning a sub-wheel
+    breadcrumb: Vec<Crumb>,
+    audio: Audio,
+    celebration: Celebration,
+    presenter: Presenter,
+    keymap: Keymap,
+    appearance: Appearance,
+    // Editor shown under the wheel on narrow windows
+    drawer_open: bool,
+    // `None` follows the language of the system
+    language: Option<Language>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 struct Choice {
     id: u32,
     label: String,
-    weight: u32,
+    weight: f64,
+    enabled: bool,
+    fairness: Fairness,
+    // Name of the saved wheel to spin when this choice wins
+    sub_wheel: Option<String>,
+    // Picture drawn in the segment, next to the label or instead of it
+    icon: Option<Icon>,
+    show_label: bool,
+    // Shown when the choice wins, empty when there is none
+    description: String,
+    tags: Vec<String>,
 }
 
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
 struct WheelChoices {
+    name: String,
     choices: Vec<Choice>,
     current_id: u32,
+    fair_rotation: FairRotation,
+    spin_settings: SpinSettings,
+    tag_filter: TagFilter,
+    #[serde(skip)]
+    history: Vec<Vec<Choice>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ChoiceOrder {
+    Alphabetical,
+    Weight,
 }
 
 impl WheelChoices {
     fn new() -> Self {
         Self {
+            name: String::new(),
             choices: Vec::new(),
             current_id: 0,
+            fair_rotation: FairRotation::default(),
+            spin_settings: SpinSettings::default(),
+            tag_filter: TagFilter::default(),
+            history: Vec::new(),
+        }
+    }
+
+    // Copy of the wheel without its undo history
+    fn snapshot(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            choices: self.choices.clone(),
+            current_id: self.current_id,
+            fair_rotation: self.fair_rotation.clone(),
+            spin_settings: self.spin_settings.clone(),
+            tag_filter: self.tag_filter.clone(),
+            history: Vec::new(),
         }
     }
 
@@ -42,39 +133,178 @@ impl WheelChoices {
     }
 
     fn add_segment(&mut self, label: String, wheel: &mut Wheel) {
+        self.save_history();
         let new_choice = self.create_choice(label);
         self.choices.push(new_choice);
         wheel.reset_rotation(&self.choices);
     }
 
+    // Adds as many generated choices as the wheel can hold, in one undo step
+    fn add_generated(&mut self, generated: Vec<(String, f64)>, replace: bool, wheel: &mut Wheel) {
+        self.save_history();
+        if replace {
+            self.choices.clear();
+        }
+        let room = constants::MAX_CHOICES.saturating_sub(self.choices.len());
+        for (label, weight) in generated.into_iter().take(room) {
+            let mut choice = self.create_choice(label);
+            choice.weight =
+                weight.clamp(constants::MIN_SEGMENT_WEIGHT, constants::MAX_SEGMENT_WEIGHT);
+            self.choices.push(choice);
+        }
+        wheel.reset_rotation(&self.choices);
+    }
+
     fn remove_segment(&mut self, id: u32, wheel: &mut Wheel) {
+        self.remove_segments(&[id], wheel);
+    }
+
+    fn remove_segments(&mut self, ids: &[u32], wheel: &mut Wheel) {
+        if self.choices.iter().any(|choice| ids.contains(&choice.id)) {
+            self.save_history();
+            self.choices.retain(|choice| !ids.contains(&choice.id));
+        }
+        wheel.reset_rotation(&self.choices);
+    }
+
+    fn clear(&mut self) {
+        if !self.choices.is_empty() {
+            self.save_history();
+            self.choices.clear();
+        }
+    }
+
+    // Enabled choices matching the tag filter, the ones the next spin can pick
+    fn in_play(&self, choice: &Choice) -> bool {
+        choice.enabled && self.tag_filter.matches(choice)
+    }
+
+    // Choices in play with their effective weight
+    fn weighted_choices(&self) -> Vec<(&Choice, f64)> {
+        let now = fairness::now();
+        self.choices
+            .iter()
+            .filter(|choice| self.in_play(choice))
+            .map(|choice| {
+                let multiplier = self.fair_rotation.multiplier(&choice.fairness, now);
+                (choice, choice.weight * multiplier as f64)
+            })
+            .collect()
+    }
+
+    fn record_wins(&mut self, ids: &[u32]) {
+        self.fair_rotation.record_wins(&mut self.choices, ids);
+    }
+
+    fn has_active(&self) -> bool {
+        self.choices.iter().any(|choice| self.in_play(choice))
+    }
+
+    fn set_enabled(&mut self, id: u32, enabled: bool) {
         let segment_index = self
             .choices
             .iter()
             .position(|segment_found| segment_found.id == id);
 
         if let Some(index) = segment_index {
-            self.choices.remove(index);
+            self.save_history();
+            self.choices[index].enabled = enabled;
         }
-        wheel.reset_rotation(&self.choices);
     }
 
-    fn empty(&mut self) -> bool {
-        self.choices.is_empty()
+    fn enable_all(&mut self) {
+        if self.choices.iter().all(|choice| choice.enabled) {
+            return;
+        }
+        self.save_history();
+        for choice in self.choices.iter_mut() {
+            choice.enabled = true;
+        }
     }
 
     fn create_choice(&mut self, label: String) -> Choice {
-        self.current_id = self.current_id + 1;
+        self.current_id += 1;
         Choice::new(label, self.current_id)
     }
-    fn rename_choice(&mut self, id: u32, new_name: String) {
+
+    // Applies what the edit dialog changes, the weight may have changed since
+    fn edit_choice(&mut self, edited: Choice) {
+        let segment_index = self
+            .choices
+            .iter()
+            .position(|segment_found| segment_found.id == edited.id);
+
+        if let Some(index) = segment_index {
+            self.save_history();
+            let choice = &mut self.choices[index];
+            choice.label = edited.label;
+            choice.sub_wheel = edited.sub_wheel;
+            choice.icon = edited.icon;
+            choice.show_label = edited.show_label;
+            choice.description = edited.description;
+            choice.tags = edited.tags;
+        }
+    }
+
+    fn set_weight(&mut self, id: u32, weight: f64) {
         let segment_index = self
             .choices
             .iter()
             .position(|segment_found| segment_found.id == id);
 
         if let Some(index) = segment_index {
-            self.choices[index].label = new_name;
+            if weight.is_finite() {
+                self.choices[index].weight =
+                    weight.clamp(constants::MIN_SEGMENT_WEIGHT, constants::MAX_SEGMENT_WEIGHT);
+            }
+        }
+    }
+
+    // `to` is the insertion index in the list before the move
+    fn move_choice(&mut self, from: usize, to: usize) {
+        if from >= self.choices.len() {
+            return;
+        }
+        let to = if to > from { to - 1 } else { to }.min(self.choices.len() - 1);
+        if to == from {
+            return;
+        }
+
+        self.save_history();
+        let choice = self.choices.remove(from);
+        self.choices.insert(to, choice);
+    }
+
+    fn shuffle(&mut self) {
+        self.save_history();
+        self.choices.shuffle(&mut rand::rng());
+    }
+
+    fn sort(&mut self, order: ChoiceOrder) {
+        self.save_history();
+        match order {
+            ChoiceOrder::Alphabetical => self
+                .choices
+                .sort_by_key(|choice| choice.label.to_lowercase()),
+            ChoiceOrder::Weight => self.choices.sort_by(|a, b| b.weight.total_cmp(&a.weight)),
+        }
+    }
+
+    fn save_history(&mut self) {
+        if self.history.len() >= constants::MAX_UNDO_HISTORY {
+            self.history.remove(0);
+        }
+        self.history.push(self.choices.clone());
+    }
+
+    fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    fn undo(&mut self, wheel: &mut Wheel) {
+        if let Some(choices) = self.history.pop() {
+            self.choices = choices;
+            wheel.reset_rotation(&self.choices);
         }
     }
 }
@@ -84,11 +314,24 @@ impl Choice {
         Self {
             id,
             label: label.to_string(),
-            weight: 1,
+            weight: 1.0,
+            enabled: true,
+            fairness: Fairness::default(),
+            sub_wheel: None,
+            icon: None,
+            show_label: true,
+            description: String::new(),
+            tags: Vec::new(),
         }
     }
 }
 
+impl Default for Choice {
+    fn default() -> Self {
+        Choice::new(String::new(), 0)
+    }
+}
+
 impl Default for App {
     fn default() -> Self {
         Self {
@@ -96,11 +339,190 @@ impl Default for App {
             input_text: String::new(),
             wheel_choices: WheelChoices::new(),
             choices_ui: ChoiceList::new(),
+            fairness_dialog: FairnessDialog::new(),
+            generator: Generator::new(),
+            library: WheelLibrary::default(),
+            results: ResultHistory::default(),
+            breadcrumb: Vec::new(),
+            audio: Audio::new(AudioSettings::default()),
+            celebration: Celebration::new(CelebrationSettings::default()),
+            presenter: Presenter::default(),
+            keymap: Keymap::default(),
+            appearance: Appearance::new(ThemeSettings::default()),
+            drawer_open: false,
+            language: None,
         }
     }
 }
 
 impl App {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let mut app = Self::default();
+        egui_extras::install_image_loaders(&cc.egui_ctx);
+        if let Some(storage) = cc.storage {
+            if let Some(wheel_choices) = eframe::get_value(storage, eframe::APP_KEY) {
+                app.wheel_choices = wheel_choices;
+                app.wheel.reset_rotation(&app.wheel_choices.choices);
+            }
+            if let Some(library) = eframe::get_value(storage, library::LIBRARY_KEY) {
+                app.library = library;
+            }
+            if let Some(results) = eframe::get_value(storage, library::RESULTS_KEY) {
+                app.results = results;
+            }
+            if let Some(settings) = eframe::get_value(storage, audio::AUDIO_KEY) {
+                app.audio.settings = settings;
+            }
+            if let Some(settings) = eframe::get_value(storage, celebration::CELEBRATION_KEY) {
+                app.celebration.settings = settings;
+            }
+            if let Some(keymap) = eframe::get_value(storage, keymap::KEYMAP_KEY) {
+                app.keymap = keymap;
+            }
+            if let Some(settings) = eframe::get_value(storage, theme::THEME_KEY) {
+                app.appearance.settings = settings;
+            }
+            if let Some(language) = eframe::get_value(storage, i18n::LANGUAGE_KEY) {
+                app.language = language;
+            }
+        }
+        i18n::set_language(app.language);
+        app
+    }
+
+    fn open_wheel(&mut self, name: &str) {
+        if let Some(wheel_choices) = self.library.get(name) {
+            self.wheel_choices = wheel_choices;
+            self.wheel.reset_rotation(&self.wheel_choices.choices);
+        }
+    }
+
+    // Replace the wheel with the sub-wheel of the winner and spin it,
+    // returns false if the sub-wheel can't be opened
+    fn open_sub_wheel(&mut self, choice: &Choice) -> bool {
+        if self.breadcrumb.len() >= constants::MAX_WHEEL_DEPTH {
+            return false;
+        }
+        let Some(child) = choice
+            .sub_wheel
+            .as_ref()
+            .and_then(|name| self.library.get(name))
+            .filter(|child| child.has_active())
+        else {
+            return false;
+        };
+
+        let parent = std::mem::replace(&mut self.wheel_choices, child);
+        self.breadcrumb.push(Crumb {
+            parent,
+            label: choice.label.clone(),
+        });
+        self.wheel.reset_rotation(&self.wheel_choices.choices);
+        self.wheel.start_spin(&self.wheel_choices);
+        true
+    }
+
+    // Go back to the parent wheel, the sub-wheel keeps its fair rotation state
+    fn close_sub_wheel(&mut self) {
+        if let Some(crumb) = self.breadcrumb.pop() {
+            let child = std::mem::replace(&mut self.wheel_choices, crumb.parent);
+            self.library.save(&child);
+            self.wheel.reset_rotation(&self.wheel_choices.choices);
+        }
+    }
+
+    // The winners of the sub-wheels are recorded with the path leading to them
+    fn record_outcome(&mut self, winners: &[Choice]) {
+        let mut path: Vec<String> = self
+            .breadcrumb
+            .iter()
+            .map(|crumb| crumb.label.clone())
+            .collect();
+        path.push(
+            winners
+                .iter()
+                .map(|choice| choice.label.clone())
+                .collect::<Vec<String>>()
+                .join(", "),
+        );
+        self.results.record(path.join(" → "));
+    }
+
+    // Tick when a boundary passes a pointer, fanfare when the wheel stops
+    fn play_sounds(&mut self, ctx: &egui::Context, previous_rotation: f64, was_spinning: bool) {
+        let dt = ctx.input(|i| i.stable_dt);
+        self.audio.follow_wheel(
+            &self.wheel,
+            &self.wheel_choices,
+            previous_rotation,
+            was_spinning,
+            dt,
+        );
+    }
+
+    // What screen readers should know about the spin
+    fn announcement(&self) -> String {
+        if self.wheel.spinning {
+            return t!("announcement-spinning");
+        }
+        let winners: Vec<&str> = self
+            .wheel
+            .winners()
+            .iter()
+            .map(|choice| choice.label.as_str())
+            .collect();
+        if winners.is_empty() {
+            return String::new();
+        }
+        t!(
+            "announcement-winners",
+            count = winners.len(),
+            names = winners.join(", ")
+        )
+    }
+
+    fn can_spin(&self) -> bool {
+        !self.wheel.spinning && self.wheel.winners().is_empty() && self.wheel_choices.has_active()
+    }
+
+    // Windows and dialogs in front of the choices, except the edit dialog of
+    // the choice list. The keyboard goes to them.
+    fn dialog_open(&self, ctx: &egui::Context) -> bool {
+        self.keymap.cheat_sheet_open
+            || self.generator.open
+            || self.fairness_dialog.open
+            || self.appearance.editor_open
+            || Modal::new(ctx, "winner_modal").is_open()
+            || Modal::new(ctx, "library_dialog").is_open()
+    }
+
+    // Shortcuts that don't belong to a single part of the window, the wheel
+    // and the choice list handle the others
+    fn handle_actions(&mut self, ctx: &egui::Context, actions: &[Action]) {
+        let editing = Modal::new(ctx, "my_dialog").is_open() || self.dialog_open(ctx);
+        for action in actions {
+            match action {
+                Action::Spin if self.can_spin() && !editing => {
+                    self.wheel.start_spin(&self.wheel_choices);
+                }
+                Action::FocusAdd if !editing => {
+                    self.drawer_open = true;
+                    ctx.memory_mut(|memory| memory.request_focus(egui::Id::new(ADD_INPUT_ID)));
+                }
+                Action::ShowShortcuts => {
+                    self.keymap.cheat_sheet_open = !self.keymap.cheat_sheet_open;
+                }
+                Action::CloseDialog => {
+                    self.keymap.cheat_sheet_open = false;
+                    self.generator.open = false;
+                    self.fairness_dialog.open = false;
+                    self.appearance.editor_open = false;
+                }
+                _ => {}
+            }
+        }
+    }
+
     fn can_add_segment(&self) -> bool {
         self.can_type_segment() && !self.input_text.is_empty()
     }
@@ -120,136 +542,158 @@ impl App {
 
 // Main loop
 impl eframe::App for App {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        // Sub-wheels come from the library, the top wheel is the one to keep
+        let wheel_choices = match self.breadcrumb.first() {
+            Some(crumb) => &crumb.parent,
+            None => &self.wheel_choices,
+        };
+        eframe::set_value(storage, eframe::APP_KEY, wheel_choices);
+        eframe::set_value(storage, library::LIBRARY_KEY, &self.library);
+        eframe::set_value(storage, library::RESULTS_KEY, &self.results);
+        eframe::set_value(storage, audio::AUDIO_KEY, &self.audio.settings);
+        eframe::set_value(
+            storage,
+            celebration::CELEBRATION_KEY,
+            &self.celebration.settings,
+        );
+        eframe::set_value(storage, keymap::KEYMAP_KEY, &self.keymap);
+        eframe::set_value(storage, theme::THEME_KEY, &self.appearance.settings);
+        eframe::set_value(storage, i18n::LANGUAGE_KEY, &self.language);
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.appearance.apply(ctx);
+        let actions = self.keymap.triggered(ctx);
+        self.handle_actions(ctx, &actions);
+        self.keymap.draw_cheat_sheet(ctx);
+        self.appearance.draw_editor(ctx);
+        self.fairness_dialog.draw(ctx, &self.wheel_choices);
+        self.generator
+            .draw(ctx, &mut self.wheel_choices, &mut self.wheel);
+
+        let previous_rotation = self.wheel.total_rotation();
+        let was_spinning = self.wheel.spinning;
+
+        // When wheel is spinning
+        let event = self.wheel.do_spin(ctx, &mut self.wheel_choices, &actions);
+        if event.is_some() {
+            self.celebration.stop();
+        }
+        match event {
+            Some(WinnerEvent::Closed(winners)) => self.record_outcome(&winners),
+            Some(WinnerEvent::OpenSubWheel(choice)) => {
+                let opened = self.open_sub_wheel(&choice);
+                if !opened {
+                    self.record_outcome(&[choice]);
+                }
+            }
+            None => {}
+        }
+
+        // Editor
+        let screen = ctx.screen_rect();
+        let mode = LayoutMode::of(screen);
+        match mode {
+            LayoutMode::Wide => {
+                egui::SidePanel::right("editor")
+                    .resizable(true)
+                    .default_width(screen.width() / 2.0)
+                    .width_range(constants::MIN_EDITOR_WIDTH..=screen.width() * 0.7)
+                    .show(ctx, |ui| {
+                        egui::TopBottomPanel::bottom("spin_bar")
+                            .show_separator_line(false)
+                            .show_inside(ui, |ui| self.draw_spin_bar(ui, mode));
+                        egui::CentralPanel::default()
+                            .show_inside(ui, |ui| self.draw_editor(ui, ctx, &actions));
+                    });
+            }
+            LayoutMode::Narrow => {
+                egui::TopBottomPanel::bottom("spin_bar")
+                    .show(ctx, |ui| self.draw_spin_bar(ui, mode));
+                if self.drawer_open {
+                    egui::TopBottomPanel::bottom("editor_drawer")
+                        .resizable(true)
+                        .default_height(screen.height() / 2.0)
+                        .height_range(constants::MIN_DRAWER_HEIGHT..=screen.height() * 0.8)
+                        .show(ctx, |ui| self.draw_editor(ui, ctx, &actions));
+                }
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |ui: &mut egui::Ui| {
             let available_rect = ui.max_rect();
             let painter = ui.painter();
 
-            // When wheel is spinning
-            self.wheel.do_spin(ctx, &mut self.wheel_choices);
-
             // Wheel
-            self.wheel.center = egui::pos2(
-                available_rect.width() * 0.25 + constants::WHEEL_OFFSET,
-                available_rect.center().y,
-            );
-
-            let available_width = available_rect.width() / 4.0;
-            let available_height = available_rect.height() / 2.0;
-            self.wheel.radius = f32::min(available_width, available_height);
-
-            self.wheel.draw(painter, &mut self.wheel_choices);
-
-            // Triangle
-            if !self.wheel_choices.empty() {
-                let triangle_center = self.wheel.get_triangle_center();
-                let triangle_points: Vec<Pos2> = vec![
-                    egui::pos2(triangle_center.x - 15.0, triangle_center.y),
-                    egui::pos2(triangle_center.x + 30.0, triangle_center.y + 20.0),
-                    egui::pos2(triangle_center.x + 30.0, triangle_center.y - 20.0),
-                ];
-                let path = PathShape::convex_polygon(
-                    triangle_points,
-                    Color32::from_rgb(200, 200, 200),
-                    Stroke::NONE,
+            self.wheel.center = available_rect.center();
+            self.wheel.radius = layout::wheel_radius(available_rect);
+            if was_spinning && !self.wheel.spinning {
+                self.celebration.start(
+                    self.wheel.winners(),
+                    ctx.input(|i| i.time),
+                    self.wheel.center,
+                    self.wheel.radius,
                 );
-                painter.add(path);
             }
-
-            // Inputs
-            let inputs_center = egui::pos2(
-                available_rect.width() * 0.75 + constants::WHEEL_OFFSET,
-                available_rect.center().y,
+            let highlight = self.celebration.highlight(ctx.input(|i| i.time));
+            self.wheel.draw(
+                painter,
+                &self.wheel_choices,
+                highlight.as_ref(),
+                self.appearance.theme(),
             );
-
-            let inputs_width = available_rect.width() / 2.0 * 0.9 - constants::WHEEL_OFFSET;
-            let inputs_height = available_rect.height() * 0.9;
-
-            let inputs_pos = egui::pos2(
-                inputs_center.x - inputs_width / 2.0,
-                inputs_center.y - inputs_height / 2.0,
+            self.celebration.draw(ctx);
+
+            // The wheel can be dragged and flicked
+            let wheel_response = ui.interact(
+                egui::Rect::from_center_size(
+                    self.wheel.center,
+                    egui::Vec2::splat(self.wheel.radius * 2.0),
+                ),
+                egui::Id::new("wheel"),
+                egui::Sense::drag(),
             );
-            // Add input UI to the right side of the container
-            egui::Area::new(egui::Id::new("inputs"))
-                .fixed_pos(inputs_pos)
-                .show(ctx, |ui| {
-                    ui.set_height(inputs_height);
-                    ui.set_width(inputs_width);
-
-                    ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
-                        // Add
-
-                        ui.horizontal(|ui| {
-                            let button_width: f32 = ui.spacing().interact_size.x;
-                            let input_width: f32 = ui.available_width() - button_width;
-
-                            egui::ScrollArea::vertical()
-                                .max_height(ui.spacing().interact_size.y * 3.0)
-                                .show(ui, |ui: &mut egui::Ui| {
-                                    let can_type = self.can_type_segment();
-                                    ui.add_enabled(
-                                        can_type,
-                                        egui::TextEdit::multiline(&mut self.input_text)
-                                            .hint_text(if can_type {
-                                                "Add a choice".to_owned()
-                                            } else {
-                                                format!(
-                                                    "Max amount of choices reached : {}",
-                                                    constants::MAX_CHOICES
-                                                )
-                                            })
-                                            .char_limit(constants::MAX_INPUT_SIZE)
-                                            .desired_width(input_width)
-                                            .desired_rows(1),
-                                    );
-                                    if ctx.input(|i| i.key_pressed(Key::Enter)) {
-                                        self.add_segment_ui();
-                                    }
-                                });
-
-                            if ui
-                                .add_enabled(self.can_add_segment(), egui::Button::new("Add"))
-                                .clicked()
-                            {
-                                self.add_segment_ui();
-                            }
-                        });
-
-                        ui.add_space(constants::SPACER_AMOUNT);
-
-                        // Choices
-                        self.choices_ui
-                            .draw(ui, &ctx, &mut self.wheel_choices, &mut self.wheel);
-
-                        ui.add_space(60.0);
-                        // Spin button
-                        if ui
-                            .add_enabled(
-                                !self.wheel.spinning && !self.wheel_choices.empty(),
-                                egui::Button::new(
-                                    egui::RichText::new("Spin the Wheel !")
-                                        .font(FontId::proportional(constants::TITLE_SIZE)),
-                                ),
-                            )
-                            .clicked()
-                        {
-                            self.wheel.start_spin();
-                        }
-                        ui.add_space(constants::SPACER_AMOUNT);
-
-                        // Clear
-                        if ui
-                            .add(egui::Button::new(
-                                egui::RichText::new("Clear the wheel")
-                                    .font(FontId::proportional(constants::TITLE_SIZE / 2.0)),
-                            ))
-                            .clicked()
-                        {
-                            self.wheel_choices.choices = vec![];
-                            self.wheel.clear();
-                        }
+            self.wheel.drag(&wheel_response, &self.wheel_choices);
+            self.wheel
+                .describe(ctx, wheel_response.id, &self.wheel_choices);
+            accessibility::announce(ctx, &self.announcement());
+            let wheel_response = if self.wheel.spinning || !self.wheel_choices.has_active() {
+                wheel_response
+            } else if wheel_response.dragged() {
+                wheel_response.on_hover_cursor(egui::CursorIcon::Grabbing)
+            } else {
+                wheel_response.on_hover_cursor(egui::CursorIcon::Grab)
+            };
+
+            self.play_sounds(ctx, previous_rotation, was_spinning);
+
+            // Segment details on hover
+            if !self.wheel.spinning && !wheel_response.dragged() {
+                if let Some((choice, probability)) = wheel_response
+                    .hover_pos()
+                    .and_then(|pos| self.wheel.choice_at(pos, &self.wheel_choices))
+                {
+                    wheel_response.on_hover_ui_at_pointer(|ui| {
+                        ui.strong(&choice.label);
+                        ui.label(t!(
+                            "chance-of-winning",
+                            percent = format!("{:.2}", probability * 100.0)
+                        ));
                     });
-                });
+                }
+            }
+
+            // Pointers
+            if self.wheel_choices.has_active() {
+                self.wheel.draw_pointers(
+                    painter,
+                    &self.wheel_choices.spin_settings,
+                    self.appearance.theme(),
+                );
+            }
         });
+
+        self.show_presenter(ctx);
     }
 }
@@ -0,0 +1,21 @@
+use eframe::egui::{
+    accesskit::{Live, Role},
+    Context, Id, Response,
+};
+
+// Name read by screen readers for the widgets showing only an icon
+pub fn set_name(response: &Response, name: impl Into<String>) {
+    let name = name.into();
+    response
+        .ctx
+        .accesskit_node_builder(response.id, |node| node.set_label(name));
+}
+
+// Live region: screen readers read its text again each time it changes
+pub fn announce(ctx: &Context, text: &str) {
+    ctx.accesskit_node_builder(Id::new("announcement"), |node| {
+        node.set_role(Role::Status);
+        node.set_live(Live::Polite);
+        node.set_label(text);
+    });
+}
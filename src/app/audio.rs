@@ -0,0 +1,412 @@
+use serde::{Deserialize, Serialize};
+
+use super::{constants, i18n::t, wheel::Wheel, WheelChoices};
+
+pub const AUDIO_KEY: &str = "audio";
+
+// Sounds the application can play
+#[derive(Debug, Clone, PartialEq)]
+pub enum SoundEvent {
+    // A segment boundary passed under a pointer, `speed` goes from 0 to 1
+    Tick { speed: f32 },
+    // The winner dialog opened
+    Fanfare,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TickModulation {
+    Pitch,
+    Volume,
+}
+
+// Sound settings, shared by every wheel
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub muted: bool,
+    pub volume: f32,
+    // What the speed of the wheel changes in the ticks
+    pub tick_modulation: TickModulation,
+    pub fanfare: bool,
+    // Files played instead of the built-in sounds
+    pub tick_file: String,
+    pub fanfare_file: String,
+}
+
+// Something able to play the sound events
+pub trait AudioBackend {
+    fn play(&mut self, event: &SoundEvent, settings: &AudioSettings) -> Result<(), String>;
+
+    // Events played so far, only the backends keeping them return some
+    #[cfg(test)]
+    fn recorded(&self) -> &[SoundEvent] {
+        &[]
+    }
+}
+
+// Plays nothing and keeps the events, for machines without audio
+#[derive(Default)]
+pub struct NullBackend {
+    events: Vec<SoundEvent>,
+}
+
+pub struct Audio {
+    pub settings: AudioSettings,
+    backend: Box<dyn AudioBackend>,
+    error: Option<String>,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            volume: 0.5,
+            tick_modulation: TickModulation::Pitch,
+            fanfare: true,
+            tick_file: String::new(),
+            fanfare_file: String::new(),
+        }
+    }
+}
+
+impl AudioBackend for NullBackend {
+    fn play(&mut self, event: &SoundEvent, _settings: &AudioSettings) -> Result<(), String> {
+        self.events.push(event.clone());
+        if self.events.len() > constants::MAX_RECORDED_SOUNDS {
+            self.events.remove(0);
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn recorded(&self) -> &[SoundEvent] {
+        &self.events
+    }
+}
+
+impl Audio {
+    pub fn new(settings: AudioSettings) -> Self {
+        Self::with_backend(settings, default_backend())
+    }
+
+    pub fn with_backend(settings: AudioSettings, backend: Box<dyn AudioBackend>) -> Self {
+        Self {
+            settings,
+            backend,
+            error: None,
+        }
+    }
+
+    pub fn play(&mut self, event: SoundEvent) {
+        if self.settings.muted {
+            return;
+        }
+        if event == SoundEvent::Fanfare && !self.settings.fanfare {
+            return;
+        }
+        if let Err(error) = self.backend.play(&event, &self.settings) {
+            self.error = Some(error);
+        }
+    }
+
+    // Tick for a wheel turning at `angular_speed` radians per second
+    pub fn tick(&mut self, angular_speed: f32) {
+        let speed = (angular_speed / constants::TICK_FULL_SPEED).clamp(0.0, 1.0);
+        self.play(SoundEvent::Tick { speed });
+    }
+
+    // Ticks when a segment boundary passed under a pointer since the wheel
+    // was at `previous_rotation`, and the fanfare when it stops. Editing the
+    // choices also moves the wheel, only spins and drags tick.
+    pub fn follow_wheel(
+        &mut self,
+        wheel: &Wheel,
+        wheel_choices: &WheelChoices,
+        previous_rotation: f64,
+        was_spinning: bool,
+        dt: f32,
+    ) {
+        let moving = was_spinning || wheel.spinning || wheel.dragging();
+        if moving && wheel.boundary_crossed(previous_rotation, wheel_choices) {
+            let speed = (wheel.total_rotation() - previous_rotation).abs() as f32;
+            self.tick(speed / dt.max(f32::EPSILON));
+        }
+        if was_spinning && !wheel.spinning {
+            self.play(SoundEvent::Fanfare);
+        }
+    }
+
+    #[cfg(test)]
+    pub fn recorded(&self) -> &[SoundEvent] {
+        self.backend.recorded()
+    }
+
+    pub fn draw_settings(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.settings.muted, t!("sound-mute"));
+        ui.add_enabled_ui(!self.settings.muted, |ui| {
+            ui.add(
+                egui::Slider::new(&mut self.settings.volume, 0.0..=1.0).text(t!("sound-volume")),
+            );
+            ui.horizontal(|ui| {
+                ui.label(t!("sound-tick-modulation"));
+                ui.selectable_value(
+                    &mut self.settings.tick_modulation,
+                    TickModulation::Pitch,
+                    t!("sound-pitch"),
+                );
+                ui.selectable_value(
+                    &mut self.settings.tick_modulation,
+                    TickModulation::Volume,
+                    t!("sound-volume"),
+                );
+            });
+            ui.checkbox(&mut self.settings.fanfare, t!("sound-fanfare"));
+
+            let mut test: Option<SoundEvent> = None;
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.settings.tick_file)
+                        .hint_text(t!("sound-tick-file")),
+                );
+                if ui
+                    .button("▶")
+                    .on_hover_text(t!("sound-play-tick"))
+                    .clicked()
+                {
+                    test = Some(SoundEvent::Tick { speed: 1.0 });
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.settings.fanfare_file)
+                        .hint_text(t!("sound-fanfare-file")),
+                );
+                if ui
+                    .button("▶")
+                    .on_hover_text(t!("sound-play-fanfare"))
+                    .clicked()
+                {
+                    test = Some(SoundEvent::Fanfare);
+                }
+            });
+            if let Some(event) = test {
+                self.error = None;
+                self.play(event);
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        if cfg!(target_arch = "wasm32") {
+            ui.weak(t!("sound-unavailable-web"));
+        } else if cfg!(not(feature = "audio")) {
+            ui.weak(t!("sound-unavailable"));
+        }
+    }
+}
+
+#[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+fn default_backend() -> Box<dyn AudioBackend> {
+    match rodio_backend::RodioBackend::new() {
+        Some(backend) => Box::new(backend),
+        None => Box::new(NullBackend::default()),
+    }
+}
+
+#[cfg(any(not(feature = "audio"), target_arch = "wasm32"))]
+fn default_backend() -> Box<dyn AudioBackend> {
+    Box::new(NullBackend::default())
+}
+
+#[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+mod rodio_backend {
+    use rodio::{source::SineWave, Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+    use std::{collections::HashMap, io::Cursor, sync::Arc, time::Duration};
+
+    use super::{AudioBackend, AudioSettings, SoundEvent, TickModulation};
+    use crate::app::i18n::t;
+
+    const TICK_FREQUENCY: f32 = 1800.0;
+    const TICK_MILLISECONDS: u64 = 15;
+    const TICK_MIN_PITCH: f32 = 0.7;
+    const TICK_MAX_PITCH: f32 = 1.4;
+    const TICK_MIN_VOLUME: f32 = 0.3;
+    // Notes of the built-in fanfare, in Hz
+    const FANFARE_NOTES: [f32; 4] = [523.25, 659.25, 783.99, 1046.5];
+    const FANFARE_NOTE_MILLISECONDS: u64 = 120;
+
+    pub struct RodioBackend {
+        // Dropping the stream stops the sound
+        _stream: OutputStream,
+        handle: OutputStreamHandle,
+        // Content of the custom sound files, by path
+        files: HashMap<String, Arc<[u8]>>,
+    }
+
+    impl RodioBackend {
+        pub fn new() -> Option<Self> {
+            let (stream, handle) = OutputStream::try_default().ok()?;
+            Some(Self {
+                _stream: stream,
+                handle,
+                files: HashMap::new(),
+            })
+        }
+
+        fn decode(&mut self, path: &str) -> Result<Decoder<Cursor<Arc<[u8]>>>, String> {
+            let bytes = match self.files.get(path) {
+                Some(bytes) => bytes.clone(),
+                None => {
+                    let bytes: Arc<[u8]> = std::fs::read(path)
+                        .map_err(|error| {
+                            t!("sound-read-error", path = path, error = error.to_string())
+                        })?
+                        .into();
+                    self.files.insert(path.to_owned(), bytes.clone());
+                    bytes
+                }
+            };
+            Decoder::new(Cursor::new(bytes))
+                .map_err(|error| t!("sound-play-error", path = path, error = error.to_string()))
+        }
+
+        fn play_source<S>(&self, source: S) -> Result<(), String>
+        where
+            S: Source<Item = f32> + Send + 'static,
+        {
+            self.handle
+                .play_raw(source)
+                .map_err(|error| error.to_string())
+        }
+    }
+
+    impl AudioBackend for RodioBackend {
+        fn play(&mut self, event: &SoundEvent, settings: &AudioSettings) -> Result<(), String> {
+            match event {
+                SoundEvent::Tick { speed } => {
+                    let (pitch, volume) = match settings.tick_modulation {
+                        TickModulation::Pitch => (
+                            TICK_MIN_PITCH + (TICK_MAX_PITCH - TICK_MIN_PITCH) * speed,
+                            settings.volume,
+                        ),
+                        TickModulation::Volume => (
+                            1.0,
+                            settings.volume * (TICK_MIN_VOLUME + (1.0 - TICK_MIN_VOLUME) * speed),
+                        ),
+                    };
+
+                    if settings.tick_file.is_empty() {
+                        let tick = SineWave::new(TICK_FREQUENCY * pitch)
+                            .take_duration(Duration::from_millis(TICK_MILLISECONDS))
+                            .amplify(volume);
+                        self.play_source(tick)
+                    } else {
+                        let path = settings.tick_file.clone();
+                        let tick = self.decode(&path)?;
+                        self.play_source(tick.convert_samples().speed(pitch).amplify(volume))
+                    }
+                }
+                SoundEvent::Fanfare => {
+                    if !settings.fanfare_file.is_empty() {
+                        let path = settings.fanfare_file.clone();
+                        let fanfare = self.decode(&path)?;
+                        return self
+                            .play_source(fanfare.convert_samples().amplify(settings.volume));
+                    }
+
+                    let sink = Sink::try_new(&self.handle).map_err(|error| error.to_string())?;
+                    for (i, note) in FANFARE_NOTES.iter().enumerate() {
+                        let length = if i + 1 == FANFARE_NOTES.len() {
+                            FANFARE_NOTE_MILLISECONDS * 3
+                        } else {
+                            FANFARE_NOTE_MILLISECONDS
+                        };
+                        sink.append(
+                            SineWave::new(*note)
+                                .take_duration(Duration::from_millis(length))
+                                .amplify(settings.volume * 0.5),
+                        );
+                    }
+                    sink.detach();
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::{Pos2, Vec2};
+
+    use super::{Audio, AudioSettings, NullBackend, SoundEvent};
+    use crate::app::{wheel::Wheel, WheelChoices};
+
+    #[test]
+    fn spin_ticks_once_per_boundary() {
+        let mut wheel_choices = WheelChoices::new();
+        let mut wheel = Wheel::new();
+        for label in ["A", "B", "C", "D"] {
+            wheel_choices.add_segment(label.to_owned(), &mut wheel);
+        }
+        wheel.center = Pos2::new(100.0, 100.0);
+        wheel.radius = 100.0;
+        let mut audio =
+            Audio::with_backend(AudioSettings::default(), Box::new(NullBackend::default()));
+
+        // The only pointer is on the right edge
+        let pointer = wheel.center + Vec2::new(wheel.radius / 2.0, 0.0);
+        let under_pointer = |wheel: &Wheel| wheel.choice_at(pointer, &wheel_choices).unwrap().0.id;
+        // Short steps, the wheel can't pass a whole segment in one
+        let dt = 0.0001;
+        let mut crossed = 0;
+        wheel.start_spin(&wheel_choices);
+        while wheel.spinning {
+            let previous_rotation = wheel.total_rotation();
+            let previous_choice = under_pointer(&wheel);
+            wheel.step(dt);
+            if under_pointer(&wheel) != previous_choice {
+                crossed += 1;
+            }
+            audio.follow_wheel(&wheel, &wheel_choices, previous_rotation, true, dt);
+        }
+
+        let events = audio.recorded();
+        let ticks = events
+            .iter()
+            .filter(|event| matches!(event, SoundEvent::Tick { .. }))
+            .count();
+        assert!(crossed > 4);
+        assert_eq!(ticks, crossed);
+        assert_eq!(events.last(), Some(&SoundEvent::Fanfare));
+        assert_eq!(events.len(), ticks + 1);
+    }
+
+    #[test]
+    fn editing_the_choices_is_silent() {
+        let mut wheel_choices = WheelChoices::new();
+        let mut wheel = Wheel::new();
+        let mut audio =
+            Audio::with_backend(AudioSettings::default(), Box::new(NullBackend::default()));
+        wheel_choices.add_segment("A".to_owned(), &mut wheel);
+        wheel_choices.add_segment("B".to_owned(), &mut wheel);
+
+        // Removing everything then undoing used to leave an infinite rotation
+        let ids: Vec<u32> = wheel_choices
+            .choices
+            .iter()
+            .map(|choice| choice.id)
+            .collect();
+        wheel_choices.remove_segments(&ids, &mut wheel);
+        let previous_rotation = wheel.total_rotation();
+        assert!(previous_rotation.is_finite());
+        wheel_choices.undo(&mut wheel);
+        audio.follow_wheel(&wheel, &wheel_choices, previous_rotation, false, 0.016);
+        assert!(!wheel.boundary_crossed(f64::INFINITY, &wheel_choices));
+
+        wheel_choices.add_segment("C".to_owned(), &mut wheel);
+        audio.follow_wheel(&wheel, &wheel_choices, previous_rotation, false, 0.016);
+        assert!(audio.recorded().is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+use eframe::egui::{self, Color32, Pos2, Stroke, Vec2};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+use super::{constants, i18n::t, Choice};
+
+pub const CELEBRATION_KEY: &str = "celebration";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CelebrationSettings {
+    // No pulse nor confetti, the winner is only highlighted
+    pub reduced_motion: bool,
+    pub confetti: bool,
+}
+
+// How the wheel shows the winners
+pub struct Highlight {
+    pub winners: Vec<u32>,
+    // From 0 (normal) to 1 (invisible) for the other segments
+    pub dim: f32,
+    // Radius of the winning segments relative to the wheel
+    pub scale: f32,
+}
+
+// Reaction of the wheel to the end of a spin, everything is computed from the
+// time since the start so it doesn't depend on the frame rate
+pub struct Celebration {
+    pub settings: CelebrationSettings,
+    winners: Vec<u32>,
+    start_time: f64,
+    particles: Vec<Particle>,
+}
+
+struct Particle {
+    origin: Pos2,
+    velocity: Vec2,
+    angular_velocity: f32,
+    size: f32,
+    color: Color32,
+}
+
+impl Default for CelebrationSettings {
+    fn default() -> Self {
+        Self {
+            reduced_motion: false,
+            confetti: true,
+        }
+    }
+}
+
+impl Celebration {
+    pub fn new(settings: CelebrationSettings) -> Self {
+        Self {
+            settings,
+            winners: Vec::new(),
+            start_time: 0.0,
+            particles: Vec::new(),
+        }
+    }
+
+    pub fn start(&mut self, winners: &[Choice], time: f64, center: Pos2, radius: f32) {
+        self.winners = winners.iter().map(|choice| choice.id).collect();
+        self.start_time = time;
+        self.particles.clear();
+        if self.settings.reduced_motion || !self.settings.confetti {
+            return;
+        }
+
+        let colors = [
+            Color32::from_rgb(51, 105, 232),
+            Color32::from_rgb(213, 15, 37),
+            Color32::from_rgb(238, 178, 17),
+            Color32::from_rgb(0, 153, 37),
+            Color32::WHITE,
+        ];
+        let mut rng = rand::rng();
+        self.particles = (0..constants::CONFETTI_COUNT)
+            .map(|_| {
+                let angle = rng.random_range(-PI..0.0);
+                let speed = rng.random_range(0.5..1.5) * radius * 2.0;
+                Particle {
+                    origin: center,
+                    velocity: Vec2::angled(angle) * speed,
+                    angular_velocity: rng.random_range(-10.0..10.0),
+                    size: rng.random_range(4.0..9.0),
+                    color: colors[rng.random_range(0..colors.len())],
+                }
+            })
+            .collect();
+    }
+
+    pub fn stop(&mut self) {
+        self.winners.clear();
+        self.particles.clear();
+    }
+
+    pub fn highlight(&self, time: f64) -> Option<Highlight> {
+        if self.winners.is_empty() {
+            return None;
+        }
+        if self.settings.reduced_motion {
+            return Some(Highlight {
+                winners: self.winners.clone(),
+                dim: constants::CELEBRATION_DIM,
+                scale: 1.0,
+            });
+        }
+
+        let elapsed = (time - self.start_time) as f32;
+        let fade_in = (elapsed / constants::CELEBRATION_FADE_TIME).min(1.0);
+        // The pulse fades out so the wheel is still once the celebration ends
+        let remaining = (1.0 - elapsed / constants::CELEBRATION_DURATION).max(0.0);
+        let pulse = (elapsed * constants::PULSE_FREQUENCY * 2.0 * PI)
+            .sin()
+            .abs();
+        Some(Highlight {
+            winners: self.winners.clone(),
+            dim: constants::CELEBRATION_DIM * fade_in,
+            scale: 1.0 + constants::PULSE_AMPLITUDE * pulse * remaining,
+        })
+    }
+
+    // Draws the confetti above everything, asks for frames until the
+    // celebration is over
+    pub fn draw(&self, ctx: &egui::Context) {
+        if self.winners.is_empty() || self.settings.reduced_motion {
+            return;
+        }
+        let elapsed = (ctx.input(|i| i.time) - self.start_time) as f32;
+        if elapsed >= constants::CELEBRATION_DURATION {
+            return;
+        }
+
+        ctx.request_repaint();
+
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Tooltip,
+            egui::Id::new("confetti"),
+        ));
+        let fade = 1.0 - elapsed / constants::CELEBRATION_DURATION;
+        for particle in self.particles.iter() {
+            let position = particle.origin
+                + particle.velocity * elapsed
+                + Vec2::new(0.0, constants::CONFETTI_GRAVITY) * elapsed * elapsed / 2.0;
+            let (sin, cos) = (particle.angular_velocity * elapsed).sin_cos();
+            let half = Vec2::new(particle.size, particle.size / 2.0) / 2.0;
+            let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+                .map(|(x, y)| {
+                    let (x, y) = (x * half.x, y * half.y);
+                    position + Vec2::new(x * cos - y * sin, x * sin + y * cos)
+                })
+                .to_vec();
+            painter.add(egui::Shape::convex_polygon(
+                corners,
+                particle.color.gamma_multiply(fade),
+                Stroke::NONE,
+            ));
+        }
+    }
+
+    pub fn draw_settings(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(
+            &mut self.settings.reduced_motion,
+            t!("celebration-reduce-motion"),
+        )
+        .on_hover_text(t!("celebration-reduce-motion-tooltip"));
+        ui.add_enabled(
+            !self.settings.reduced_motion,
+            egui::Checkbox::new(&mut self.settings.confetti, t!("celebration-confetti")),
+        );
+    }
+}
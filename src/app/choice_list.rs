@@ -1,115 +1,619 @@
-use egui::{Context, Frame, Label, RichText, Rounding};
+use std::collections::HashMap;
+
+use egui::{Context, Frame, Label, RichText, Rounding, Sense, Stroke, Vec2};
 use egui_modal::Modal;
 
 use crate::app::constants;
 
-use super::{wheel::Wheel, Choice, WheelChoices};
+use super::{
+    accessibility, i18n::t, icon::Icon, keymap::Action, tags, wheel::Wheel, Choice, ChoiceOrder,
+    WheelChoices,
+};
 
 pub struct ChoiceList {
     choice_to_rename: Option<Choice>,
     rename_input: String,
+    sub_wheel_input: Option<String>,
+    icon_path: String,
+    emoji_input: String,
+    icon_error: Option<String>,
+    tag_input: String,
+    // Row moved with the arrow keys
+    selected: Option<u32>,
+    scroll_to_selected: bool,
+    // Jump of the list to a selected row that isn't laid out
+    scroll_offset: Option<f32>,
+    // Height of the rows as last drawn
+    row_height: Option<f32>,
 }
 impl ChoiceList {
     pub fn new() -> Self {
         Self {
             choice_to_rename: None,
             rename_input: String::new(),
+            sub_wheel_input: None,
+            icon_path: String::new(),
+            emoji_input: String::new(),
+            icon_error: None,
+            tag_input: String::new(),
+            selected: None,
+            scroll_to_selected: false,
+            scroll_offset: None,
+            row_height: None,
         }
     }
 
+    fn open_editor(&mut self, choice: &Choice, modal: &Modal) {
+        self.choice_to_rename = Some(choice.clone());
+        self.rename_input = choice.label.clone();
+        self.sub_wheel_input = choice.sub_wheel.clone();
+        self.emoji_input.clear();
+        self.icon_error = None;
+        self.tag_input.clear();
+        modal.open();
+    }
+
+    fn selected_index(&self, wheel_choices: &WheelChoices) -> Option<usize> {
+        let id = self.selected?;
+        wheel_choices
+            .choices
+            .iter()
+            .position(|choice| choice.id == id)
+    }
+
+    fn select(&mut self, wheel_choices: &WheelChoices, index: usize) {
+        self.selected = wheel_choices.choices.get(index).map(|choice| choice.id);
+        self.scroll_to_selected = true;
+    }
+
+    fn handle_actions(
+        &mut self,
+        actions: &[Action],
+        wheel_choices: &mut WheelChoices,
+        wheel: &mut Wheel,
+        modal: &Modal,
+    ) {
+        let count = wheel_choices.choices.len();
+        if count == 0 {
+            return;
+        }
+
+        for action in actions {
+            let index = self.selected_index(wheel_choices);
+            match (action, index) {
+                (Action::PreviousChoice, Some(i)) => {
+                    self.select(wheel_choices, i.saturating_sub(1))
+                }
+                (Action::PreviousChoice, None) => self.select(wheel_choices, count - 1),
+                (Action::NextChoice, Some(i)) => self.select(wheel_choices, (i + 1).min(count - 1)),
+                (Action::NextChoice, None) => self.select(wheel_choices, 0),
+                (Action::IncreaseWeight | Action::DecreaseWeight, Some(i)) => {
+                    let choice = &wheel_choices.choices[i];
+                    let weight = if *action == Action::IncreaseWeight {
+                        choice.weight + 1.0
+                    } else {
+                        choice.weight - 1.0
+                    };
+                    if (constants::MIN_SEGMENT_WEIGHT..=constants::MAX_SEGMENT_WEIGHT)
+                        .contains(&weight)
+                    {
+                        let id = choice.id;
+                        wheel_choices.save_history();
+                        wheel_choices.set_weight(id, weight);
+                    }
+                }
+                (Action::Rename, Some(i)) => {
+                    let choice = wheel_choices.choices[i].clone();
+                    self.open_editor(&choice, modal);
+                }
+                (Action::Remove, Some(i)) => {
+                    let id = wheel_choices.choices[i].id;
+                    wheel_choices.remove_segment(id, wheel);
+                    // Keep a row selected to remove several choices in a row
+                    let remaining = wheel_choices.choices.len();
+                    if remaining > 0 {
+                        self.select(wheel_choices, i.min(remaining - 1));
+                    } else {
+                        self.selected = None;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Picture of the choice, loaded from a file or picked among the emoji
+    fn draw_icon_editor(
+        ui: &mut egui::Ui,
+        choice: &mut Choice,
+        icon_path: &mut String,
+        emoji_input: &mut String,
+        error: &mut Option<String>,
+    ) {
+        let mut result: Option<Result<Icon, String>> = None;
+        ui.horizontal(|ui| {
+            ui.label(t!("icon-label"));
+            match &choice.icon {
+                Some(icon) => {
+                    let size = ui.spacing().interact_size.y * 2.0;
+                    ui.add(icon.image().fit_to_exact_size(Vec2::splat(size)));
+                    if ui.button(t!("icon-remove")).clicked() {
+                        choice.icon = None;
+                        choice.show_label = true;
+                    }
+                }
+                None => {
+                    ui.weak(t!("icon-none"));
+                }
+            }
+        });
+        if let Some(loaded) = Self::draw_icon_file(ui, icon_path) {
+            result = Some(loaded);
+        }
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(emoji_input)
+                    .hint_text(t!("icon-emoji-hint"))
+                    .desired_width(ui.spacing().interact_size.x * 2.0),
+            );
+            if ui
+                .add_enabled(
+                    !emoji_input.trim().is_empty(),
+                    egui::Button::new(t!("icon-use-emoji")),
+                )
+                .clicked()
+            {
+                result = Some(Icon::from_emoji(emoji_input));
+            }
+        });
+        ui.add_enabled(
+            choice.icon.is_some(),
+            egui::Checkbox::new(&mut choice.show_label, t!("icon-show-label")),
+        );
+
+        match result {
+            Some(Ok(icon)) => {
+                choice.icon = Some(icon);
+                *error = None;
+            }
+            Some(Err(message)) => *error = Some(message),
+            None => {}
+        }
+        if let Some(error) = error {
+            ui.colored_label(ui.visuals().error_fg_color, error.as_str());
+        }
+    }
+
+    // Path of a picture to load, `Some` once the user asked to load it
+    #[cfg(not(target_arch = "wasm32"))]
+    fn draw_icon_file(ui: &mut egui::Ui, icon_path: &mut String) -> Option<Result<Icon, String>> {
+        let mut result = None;
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(icon_path).hint_text(t!("icon-file-hint")));
+            if ui
+                .add_enabled(
+                    !icon_path.trim().is_empty(),
+                    egui::Button::new(t!("icon-load")),
+                )
+                .clicked()
+            {
+                result = Some(Icon::from_file(icon_path.trim()));
+            }
+        });
+        result
+    }
+
+    // The browser doesn't give access to the files
+    #[cfg(target_arch = "wasm32")]
+    fn draw_icon_file(ui: &mut egui::Ui, _icon_path: &mut String) -> Option<Result<Icon, String>> {
+        ui.weak(t!("icon-file-unavailable-web"));
+        None
+    }
+
     pub fn draw(
         &mut self,
         ui: &mut egui::Ui,
         ctx: &Context,
         wheel_choices: &mut WheelChoices,
         wheel: &mut Wheel,
+        wheel_names: &[String],
+        actions: &[Action],
     ) {
         let enabled = !wheel.spinning;
         let modal = Modal::new(ctx, "my_dialog");
 
+        // Keyboard
+        if modal.is_open() {
+            if actions.contains(&Action::CloseDialog) {
+                modal.close();
+            }
+        } else if enabled {
+            self.handle_actions(actions, wheel_choices, wheel, &modal);
+        }
+
         modal.show(|ui| {
-            modal.title(ui, "Edit this choice");
+            modal.title(ui, t!("edit-choice"));
             modal.frame(ui, |ui| {
                 egui::ScrollArea::vertical()
                     .max_height(ui.spacing().interact_size.y * 3.0)
                     .show(ui, |ui: &mut egui::Ui| {
                         ui.add(
                             egui::TextEdit::multiline(&mut self.rename_input)
-                                .hint_text("Rename the choice")
+                                .hint_text(t!("rename-choice-hint"))
                                 .char_limit(constants::MAX_INPUT_SIZE)
                                 .desired_rows(1),
                         )
-                    })
+                    });
+
+                // A winning choice can open another saved wheel
+                ui.horizontal(|ui| {
+                    ui.label(t!("opens-wheel-label"));
+                    egui::ComboBox::from_id_salt("sub_wheel")
+                        .selected_text(
+                            self.sub_wheel_input
+                                .clone()
+                                .unwrap_or_else(|| t!("no-sub-wheel")),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.sub_wheel_input,
+                                None,
+                                t!("no-sub-wheel"),
+                            );
+                            for name in wheel_names
+                                .iter()
+                                .filter(|name| **name != wheel_choices.name)
+                            {
+                                ui.selectable_value(
+                                    &mut self.sub_wheel_input,
+                                    Some(name.clone()),
+                                    name,
+                                );
+                            }
+                        });
+                });
+
+                if let Some(choice) = &mut self.choice_to_rename {
+                    tags::draw_editor(ui, &mut choice.tags, &mut self.tag_input);
+
+                    ui.label(t!("description-label"));
+                    egui::ScrollArea::vertical()
+                        .id_salt("description")
+                        .max_height(ui.spacing().interact_size.y * 6.0)
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::multiline(&mut choice.description)
+                                    .hint_text(t!("description-hint"))
+                                    .char_limit(constants::MAX_DESCRIPTION_SIZE)
+                                    .desired_rows(3),
+                            )
+                        });
+
+                    Self::draw_icon_editor(
+                        ui,
+                        choice,
+                        &mut self.icon_path,
+                        &mut self.emoji_input,
+                        &mut self.icon_error,
+                    );
+                }
             });
 
             modal.buttons(ui, |ui| {
-                modal.button(ui, "Cancel");
-                if modal.button(ui, "Confirm").clicked() {
-                    if let Some(choice) = &self.choice_to_rename {
-                        wheel_choices.rename_choice(choice.id, self.rename_input.clone());
+                modal.button(ui, t!("cancel-button"));
+                if modal.button(ui, t!("confirm-button")).clicked() {
+                    if let Some(choice) = self.choice_to_rename.take() {
+                        wheel_choices.edit_choice(Choice {
+                            label: self.rename_input.clone(),
+                            sub_wheel: self.sub_wheel_input.clone(),
+                            ..choice
+                        });
                     }
                 }
             });
         });
 
-        egui::ScrollArea::vertical()
-            .max_height(ui.available_height() * 0.75)
-            .show(ui, |ui| {
+        ui.horizontal(|ui| {
+            let can_reorder = enabled && wheel_choices.choices.len() > 1;
+
+            if ui
+                .add_enabled(can_reorder, egui::Button::new(t!("shuffle-button")))
+                .clicked()
+            {
+                wheel_choices.shuffle();
+            }
+
+            if ui
+                .add_enabled(
+                    can_reorder,
+                    egui::Button::new(t!("sort-alphabetical-button")),
+                )
+                .clicked()
+            {
+                wheel_choices.sort(ChoiceOrder::Alphabetical);
+            }
+
+            if ui
+                .add_enabled(can_reorder, egui::Button::new(t!("sort-weight-button")))
+                .clicked()
+            {
+                wheel_choices.sort(ChoiceOrder::Weight);
+            }
+
+            if ui
+                .add_enabled(
+                    enabled && wheel_choices.choices.iter().any(|choice| !choice.enabled),
+                    egui::Button::new(t!("enable-all-button")),
+                )
+                .clicked()
+            {
+                wheel_choices.enable_all();
+            }
+
+            if ui
+                .add_enabled(
+                    enabled && wheel_choices.can_undo(),
+                    egui::Button::new(t!("undo-button")),
+                )
+                .clicked()
+            {
+                wheel_choices.undo(wheel);
+            }
+        });
+
+        wheel_choices
+            .tag_filter
+            .draw(ui, &wheel_choices.choices, enabled);
+
+        // Only the visible rows are laid out, wheels can have thousands of choices
+        let row_height = self.row_height.unwrap_or(ui.spacing().interact_size.y);
+        let mut scroll_area = egui::ScrollArea::vertical().max_height(ui.available_height() * 0.75);
+        if let Some(offset) = self.scroll_offset.take() {
+            scroll_area = scroll_area.vertical_scroll_offset(offset);
+        }
+        scroll_area.show_rows(
+            ui,
+            row_height,
+            wheel_choices.choices.len(),
+            |ui, row_range| {
                 let mut choice_to_remove: Option<Choice> = None;
+                let mut weight_change: Option<(u32, f64, bool)> = None;
+                let mut weight_drag_started = false;
+                let mut choice_move: Option<(usize, usize)> = None;
+                let mut choice_toggle: Option<(u32, bool)> = None;
+
+                // Probability of each enabled choice
+                let probabilities: HashMap<u32, f64> = Wheel::get_segments(wheel_choices)
+                    .into_iter()
+                    .map(|segment| (segment.choice.id, segment.probability))
+                    .collect();
+
+                let buttons_width: f32 = ui.spacing().interact_size.x * 7.5;
+                // Labels keep a readable width on narrow windows
+                let available_width: f32 =
+                    (ui.available_width() - buttons_width).max(ui.spacing().interact_size.x * 2.0);
+
+                for (index, choice) in wheel_choices
+                    .choices
+                    .iter()
+                    .enumerate()
+                    .skip(row_range.start)
+                    .take(row_range.len())
+                {
+                    let row = ui.horizontal(|ui| {
+                        if enabled {
+                            ui.dnd_drag_source(
+                                egui::Id::new(("choice_drag", choice.id)),
+                                index,
+                                |ui| ui.label("☰"),
+                            );
+                        } else {
+                            ui.add_enabled(false, Label::new("☰"));
+                        }
+
+                        let mut choice_enabled = choice.enabled;
+                        if ui
+                            .add_enabled(enabled, egui::Checkbox::without_text(&mut choice_enabled))
+                            .on_hover_text(if choice.enabled {
+                                t!("disable-choice")
+                            } else {
+                                t!("enable-choice")
+                            })
+                            .changed()
+                        {
+                            choice_toggle = Some((choice.id, choice_enabled));
+                        }
 
-                let buttons_width: f32 = ui.spacing().interact_size.x * 4.0;
-                let available_width: f32 = ui.available_width() - buttons_width;
+                        let mut label_width = available_width;
+                        if let Some(icon) = &choice.icon {
+                            let size = ui.spacing().interact_size.y;
+                            ui.add(icon.image().fit_to_exact_size(Vec2::splat(size)));
+                            label_width -= size + ui.spacing().item_spacing.x;
+                        }
 
-                for choice in wheel_choices.choices.iter_mut() {
-                    ui.horizontal(|ui| {
+                        // Disabled choices and the ones left out by the tag filter are greyed out
+                        let (fill, text_color) = if wheel_choices.in_play(choice) {
+                            (
+                                ui.style().visuals.widgets.active.bg_fill,
+                                ui.style().visuals.widgets.active.text_color(),
+                            )
+                        } else {
+                            (
+                                ui.style().visuals.faint_bg_color,
+                                ui.style().visuals.weak_text_color(),
+                            )
+                        };
+
+                        // The selected row is outlined, clicking a row selects it
+                        let stroke = if self.selected == Some(choice.id) {
+                            ui.style().visuals.selection.stroke
+                        } else {
+                            Stroke::NONE
+                        };
                         Frame::default()
-                            .fill(ui.style().visuals.widgets.active.bg_fill)
+                            .fill(fill)
+                            .stroke(stroke)
                             .rounding(Rounding::same(4.0))
                             .show(ui, |ui| {
-                                ui.add_sized(
-                                    [available_width, ui.spacing().interact_size.y],
-                                    Label::new(
-                                        RichText::new(&choice.label)
-                                            .color(ui.style().visuals.widgets.active.text_color()),
+                                if ui
+                                    .add_sized(
+                                        [label_width, ui.spacing().interact_size.y],
+                                        Label::new(RichText::new(&choice.label).color(text_color))
+                                            .truncate()
+                                            .sense(Sense::click()),
                                     )
-                                    .truncate(),
-                                );
+                                    .clicked()
+                                {
+                                    self.selected = Some(choice.id);
+                                }
                             });
 
-                        ui.add(Label::new(format!("Weight : {}", choice.weight)));
+                        if !choice.tags.is_empty() {
+                            ui.label("🏷").on_hover_text(choice.tags.join(", "));
+                        }
 
-                        if ui
-                            .add_enabled(
-                                enabled && choice.weight < constants::MAX_SEGMENT_WEIGHT,
-                                egui::Button::new("+"),
-                            )
-                            .clicked()
-                        {
-                            choice.weight += 1;
+                        if let Some(sub_wheel) = &choice.sub_wheel {
+                            ui.label("🔗")
+                                .on_hover_text(t!("opens-wheel", name = sub_wheel.as_str()));
                         }
 
-                        if ui
-                            .add_enabled(enabled && choice.weight > 1, egui::Button::new("-"))
-                            .clicked()
-                        {
-                            choice.weight -= 1;
+                        ui.add(Label::new(t!("weight-label")));
+                        let mut weight = choice.weight;
+                        let weight_response = ui.add_enabled(
+                            enabled,
+                            egui::DragValue::new(&mut weight)
+                                .range(
+                                    constants::MIN_SEGMENT_WEIGHT..=constants::MAX_SEGMENT_WEIGHT,
+                                )
+                                .speed((choice.weight * 0.01).max(0.01))
+                                .max_decimals(3),
+                        );
+                        // A drag is a single change in the history
+                        if weight_response.drag_started() {
+                            weight_drag_started = true;
                         }
+                        if weight_response.changed() {
+                            weight_change = Some((choice.id, weight, !weight_response.dragged()));
+                        }
+
+                        let increase_response = ui.add_enabled(
+                            enabled && choice.weight + 1.0 <= constants::MAX_SEGMENT_WEIGHT,
+                            egui::Button::new("+"),
+                        );
+                        accessibility::set_name(
+                            &increase_response,
+                            t!("increase-weight-of", name = choice.label.as_str()),
+                        );
+                        if increase_response.clicked() {
+                            weight_change = Some((choice.id, choice.weight + 1.0, true));
+                        }
+
+                        let decrease_response = ui.add_enabled(
+                            enabled && choice.weight - 1.0 >= constants::MIN_SEGMENT_WEIGHT,
+                            egui::Button::new("-"),
+                        );
+                        accessibility::set_name(
+                            &decrease_response,
+                            t!("decrease-weight-of", name = choice.label.as_str()),
+                        );
+                        if decrease_response.clicked() {
+                            weight_change = Some((choice.id, choice.weight - 1.0, true));
+                        }
+
+                        let probability = probabilities
+                            .get(&choice.id)
+                            .map(|probability| format!("{:.1} %", probability * 100.0))
+                            .unwrap_or_else(|| "–".to_owned());
+                        ui.add_sized(
+                            [ui.spacing().interact_size.x, ui.spacing().interact_size.y],
+                            Label::new(probability),
+                        )
+                        .on_hover_text(t!("chance-next-spin"));
 
-                        if ui.add_enabled(enabled, egui::Button::new("✏")).clicked() {
-                            self.choice_to_rename = Some(choice.clone());
-                            self.rename_input = choice.label.clone();
-                            modal.open();
+                        let edit_response = ui
+                            .add_enabled(enabled, egui::Button::new("✏"))
+                            .on_hover_text(t!("edit-choice"));
+                        accessibility::set_name(
+                            &edit_response,
+                            t!("edit-choice-of", name = choice.label.as_str()),
+                        );
+                        if edit_response.clicked() {
+                            self.open_editor(choice, &modal);
                         }
 
-                        if ui.add_enabled(enabled, egui::Button::new("🗑")).clicked() {
+                        let remove_response = ui
+                            .add_enabled(enabled, egui::Button::new("🗑"))
+                            .on_hover_text(t!("remove-choice"));
+                        accessibility::set_name(
+                            &remove_response,
+                            t!("remove-choice-of", name = choice.label.as_str()),
+                        );
+                        if remove_response.clicked() {
                             choice_to_remove = Some(choice.clone());
                         }
                     });
+
+                    let response = row.response;
+                    self.row_height = Some(response.rect.height());
+                    if self.scroll_to_selected && self.selected == Some(choice.id) {
+                        response.scroll_to_me(None);
+                        self.scroll_to_selected = false;
+                    }
+
+                    // Drop target: above or below this row depending on the pointer
+                    if let (Some(pointer), Some(_)) = (
+                        ui.ctx().pointer_interact_pos(),
+                        response.dnd_hover_payload::<usize>(),
+                    ) {
+                        let rect = response.rect;
+                        let insert_below = pointer.y > rect.center().y;
+                        let line_y = if insert_below {
+                            rect.bottom() + ui.spacing().item_spacing.y / 2.0
+                        } else {
+                            rect.top() - ui.spacing().item_spacing.y / 2.0
+                        };
+                        ui.painter()
+                            .hline(rect.x_range(), line_y, ui.visuals().selection.stroke);
+
+                        if let Some(from) = response.dnd_release_payload::<usize>() {
+                            let to = if insert_below { index + 1 } else { index };
+                            choice_move = Some((*from, to));
+                        }
+                    }
+                }
+
+                // The selected row is off screen, jump to it on the next frame
+                if self.scroll_to_selected {
+                    if let Some(index) = self.selected_index(wheel_choices) {
+                        self.scroll_offset =
+                            Some(index as f32 * (row_height + ui.spacing().item_spacing.y));
+                    }
+                    self.scroll_to_selected = false;
+                }
+
+                if let Some((id, choice_enabled)) = choice_toggle {
+                    wheel_choices.set_enabled(id, choice_enabled);
+                }
+
+                if weight_drag_started {
+                    wheel_choices.save_history();
+                }
+
+                if let Some((id, weight, save_history)) = weight_change {
+                    if save_history {
+                        wheel_choices.save_history();
+                    }
+                    wheel_choices.set_weight(id, weight);
+                }
+
+                if let Some((from, to)) = choice_move {
+                    wheel_choices.move_choice(from, to);
                 }
 
                 if let Some(choice) = choice_to_remove {
                     wheel_choices.remove_segment(choice.id, wheel);
                 }
-            });
+            },
+        );
     }
 }
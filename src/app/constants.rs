@@ -1,14 +1,69 @@
 pub const SPACER_AMOUNT: f32 = 20.0;
 pub const TITLE_SIZE: f32 = 30.0;
-pub const MAX_CHOICES: usize = 75;
+pub const MAX_CHOICES: usize = 10_000;
 pub const MAX_INPUT_SIZE: usize = 500;
-pub const WHEEL_OFFSET: f32 = 20.0;
-pub const STEPS: u8 = 200;
-pub const SPIN_VELOCITY_MIN: f32 = 0.4;
-pub const SPIN_VELOCITY_MAX: f32 = 0.6;
-pub const BREAKING_PERCENT: f32 = 0.995;
-pub const MIN_SPEED: f32 = 0.00020;
+pub const MAX_DESCRIPTION_SIZE: usize = 5000;
+pub const MAX_TAGS: usize = 10;
+pub const MAX_TAG_LENGTH: usize = 30;
+pub const STEPS: usize = 200;
+pub const MIN_STEP_LENGTH: f32 = 2.0;
 pub const MAX_RANGE_TEXT_LENGTH: usize = 15;
 pub const MIN_TEXT_SIZE: usize = 15;
 pub const MAX_TEXT_SIZE: usize = 100;
-pub const MAX_SEGMENT_WEIGHT: u32 = 99;
+pub const MIN_SEGMENT_WEIGHT: f64 = 0.001;
+pub const MAX_SEGMENT_WEIGHT: f64 = 1e12;
+pub const MAX_UNDO_HISTORY: usize = 50;
+pub const MIN_FAIRNESS_MULTIPLIER: f32 = 0.01;
+#[cfg(not(target_arch = "wasm32"))]
+pub const SIMULATION_PROGRESS_STEP: u64 = 1000;
+#[cfg(target_arch = "wasm32")]
+pub const SIMULATION_FRAME_SPINS: u64 = 20_000;
+#[cfg(not(target_arch = "wasm32"))]
+pub const CLI_SIMULATION_SPINS: u64 = 1_000_000;
+pub const GUI_SIMULATION_SPINS: u64 = 100_000;
+pub const MAX_SIMULATION_SPINS: u64 = 100_000_000;
+pub const FAIRNESS_SIGNIFICANCE: f64 = 0.01;
+pub const GAMMA_MAX_ITERATIONS: usize = 1000;
+pub const FRAME_DURATION: f32 = 1.0 / 60.0;
+pub const FLICK_SAMPLE_TIME: f64 = 0.1;
+pub const FLICK_MIN_VELOCITY: f32 = 0.05;
+pub const FLICK_MAX_VELOCITY: f32 = 0.9;
+pub const MAX_POINTERS: usize = 12;
+pub const MAX_RESULTS: usize = 50;
+pub const MAX_WHEEL_DEPTH: usize = 10;
+pub const MAX_GENERATED_CHOICES: usize = 10_000;
+pub const GENERATOR_PREVIEW_SIZE: usize = 8;
+pub const MIN_SPIN_DURATION: f32 = 0.5;
+pub const MAX_SPIN_DURATION: f32 = 60.0;
+pub const MAX_MIN_ROTATIONS: u32 = 50;
+pub const TURNS_PER_SECOND: f32 = 1.0;
+pub const EXPONENTIAL_DECAY: f32 = 5.0;
+pub const BOUNCE_TIME: f32 = 0.15;
+pub const BOUNCE_ANGLE: f32 = 0.12;
+pub const MAX_FRAME_TIME: f32 = 0.1;
+pub const PREVIEW_BINS: usize = 20;
+pub const PREVIEW_HEIGHT: f32 = 60.0;
+pub const PREVIEW_CURVE_POINTS: usize = 50;
+pub const PREVIEW_BOUNCE_SCALE: f32 = 0.05;
+pub const TICK_FULL_SPEED: f32 = 40.0;
+pub const MAX_RECORDED_SOUNDS: usize = 1000;
+pub const CELEBRATION_DURATION: f32 = 3.0;
+pub const CELEBRATION_FADE_TIME: f32 = 0.3;
+pub const CELEBRATION_DIM: f32 = 0.6;
+pub const PULSE_FREQUENCY: f32 = 1.5;
+pub const PULSE_AMPLITUDE: f32 = 0.05;
+pub const WINNER_OUTLINE: f32 = 3.0;
+pub const CONFETTI_COUNT: usize = 150;
+pub const CONFETTI_GRAVITY: f32 = 600.0;
+pub const PRESENTER_WHEEL_SIZE: f32 = 0.9;
+pub const MAX_PALETTE_COLORS: usize = 12;
+pub const NARROW_LAYOUT_WIDTH: f32 = 800.0;
+pub const MIN_FONT_SCALE: f32 = 0.6;
+pub const WHEEL_FILL: f32 = 0.88;
+pub const MIN_EDITOR_WIDTH: f32 = 320.0;
+pub const MIN_DRAWER_HEIGHT: f32 = 120.0;
+pub const POINTER_REFERENCE_RADIUS: f32 = 300.0;
+#[cfg(not(target_arch = "wasm32"))]
+pub const MAX_ICON_BYTES: usize = 1024 * 1024;
+pub const ICON_TEXTURE_SIZE: u32 = 256;
+pub const MIN_ICON_SIZE: f32 = 8.0;
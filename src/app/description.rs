@@ -0,0 +1,172 @@
+use egui::{RichText, Ui};
+
+// Piece of a line with its style
+#[derive(Debug, PartialEq)]
+enum Span<'a> {
+    Text(&'a str),
+    Bold(&'a str),
+    Italic(&'a str),
+    Code(&'a str),
+    Link { text: &'a str, url: &'a str },
+}
+
+// Links can only open web pages and mails
+const LINK_SCHEMES: [&str; 3] = ["https://", "http://", "mailto:"];
+
+// Small part of markdown: `# headings`, `- lists`, **bold**, *italic*,
+// `code`, [links](https://example.com) and bare https:// links
+pub fn draw(ui: &mut Ui, text: &str) {
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            ui.add_space(ui.spacing().item_spacing.y);
+            continue;
+        }
+
+        let (line, heading, bullet) = if let Some(rest) = line.trim_start().strip_prefix('#') {
+            (rest.trim_start_matches('#').trim_start(), true, false)
+        } else if let Some(rest) = line
+            .trim_start()
+            .strip_prefix("- ")
+            .or_else(|| line.trim_start().strip_prefix("* "))
+        {
+            (rest, false, true)
+        } else {
+            (line, false, false)
+        };
+
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            if bullet {
+                ui.label("  • ");
+            }
+            for span in spans(line) {
+                let style = |text: &str| {
+                    let text = RichText::new(text);
+                    if heading {
+                        text.heading()
+                    } else {
+                        text
+                    }
+                };
+                match span {
+                    Span::Text(text) => ui.label(style(text)),
+                    Span::Bold(text) => ui.label(style(text).strong()),
+                    Span::Italic(text) => ui.label(style(text).italics()),
+                    Span::Code(text) => ui.label(style(text).code()),
+                    Span::Link { text, url } => ui.hyperlink_to(style(text), url),
+                };
+            }
+        });
+    }
+}
+
+fn spans(line: &str) -> Vec<Span<'_>> {
+    let mut spans = Vec::new();
+    let mut rest = line;
+    while !rest.is_empty() {
+        match next_styled(rest) {
+            Some((start, span, end)) => {
+                if start > 0 {
+                    spans.push(Span::Text(&rest[..start]));
+                }
+                spans.push(span);
+                rest = &rest[end..];
+            }
+            None => {
+                spans.push(Span::Text(rest));
+                break;
+            }
+        }
+    }
+    spans
+}
+
+// First styled span of `text`, with where it starts and ends. Markers
+// without their closing half are left as text.
+fn next_styled(text: &str) -> Option<(usize, Span<'_>, usize)> {
+    let mut from = 0;
+    while let Some(offset) = text[from..].find(['*', '`', '[', 'h']) {
+        let start = from + offset;
+        let rest = &text[start..];
+        let found = if let Some(inner) = rest.strip_prefix("**") {
+            closed(inner, "**").map(|(content, length)| (Span::Bold(content), 2 + length))
+        } else if let Some(inner) = rest.strip_prefix('*') {
+            closed(inner, "*").map(|(content, length)| (Span::Italic(content), 1 + length))
+        } else if let Some(inner) = rest.strip_prefix('`') {
+            closed(inner, "`").map(|(content, length)| (Span::Code(content), 1 + length))
+        } else if let Some(inner) = rest.strip_prefix('[') {
+            closed(inner, "](").and_then(|(label, label_length)| {
+                let after = &inner[label_length..];
+                closed(after, ")")
+                    .filter(|(url, _)| allowed_link(url))
+                    .map(|(url, url_length)| {
+                        (
+                            Span::Link { text: label, url },
+                            1 + label_length + url_length,
+                        )
+                    })
+            })
+        } else if rest.starts_with("https://") || rest.starts_with("http://") {
+            let length = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            // A sentence can end right after a link
+            let url = rest[..length].trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+            Some((Span::Link { text: url, url }, url.len()))
+        } else {
+            None
+        };
+
+        if let Some((span, length)) = found {
+            return Some((start, span, start + length));
+        }
+        from = start + rest.chars().next().map_or(1, char::len_utf8);
+    }
+    None
+}
+
+// Content up to `end` and the length consumed with `end`, `None` if the
+// span isn't closed, is empty or starts with a space like in "2 * 3"
+fn closed<'a>(text: &'a str, end: &str) -> Option<(&'a str, usize)> {
+    let position = text.find(end)?;
+    let content = &text[..position];
+    (!content.is_empty() && !content.starts_with(char::is_whitespace))
+        .then_some((content, position + end.len()))
+}
+
+// Other schemes, like javascript: or file:, are left as text
+fn allowed_link(url: &str) -> bool {
+    LINK_SCHEMES.iter().any(|scheme| {
+        url.get(..scheme.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(scheme))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{spans, Span};
+
+    #[test]
+    fn links_need_an_allowed_scheme() {
+        assert_eq!(
+            spans("[site](https://example.com) [mail](MAILTO:a@example.com)"),
+            [
+                Span::Link {
+                    text: "site",
+                    url: "https://example.com"
+                },
+                Span::Text(" "),
+                Span::Link {
+                    text: "mail",
+                    url: "MAILTO:a@example.com"
+                },
+            ]
+        );
+        for text in [
+            "[run](javascript:alert(1))",
+            "[open](file:///etc/passwd)",
+            "[app](steam://run/1)",
+        ] {
+            assert_eq!(spans(text), [Span::Text(text)], "{text}");
+        }
+    }
+}
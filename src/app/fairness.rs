@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use super::{constants, i18n::t, Choice};
+
+const SECONDS_PER_DAY: f32 = 86_400.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RecoveryUnit {
+    Spins,
+    Days,
+}
+
+// Settings of the "fair rotation" mode, saved with the wheel
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FairRotation {
+    pub enabled: bool,
+    // Multiplier applied to the weight of a choice when it wins
+    pub decay: f32,
+    // Part of the lost weight given back after each spin or day
+    pub recovery: f32,
+    pub recovery_unit: RecoveryUnit,
+}
+
+// Weight multiplier of a single choice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Fairness {
+    multiplier: f32,
+    updated_at: u64,
+}
+
+impl Default for FairRotation {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            decay: 0.5,
+            recovery: 0.25,
+            recovery_unit: RecoveryUnit::Spins,
+        }
+    }
+}
+
+impl Default for Fairness {
+    fn default() -> Self {
+        Self {
+            multiplier: 1.0,
+            updated_at: 0,
+        }
+    }
+}
+
+impl FairRotation {
+    pub fn multiplier(&self, fairness: &Fairness, now: u64) -> f32 {
+        if !self.enabled {
+            return 1.0;
+        }
+
+        match self.recovery_unit {
+            RecoveryUnit::Spins => fairness.multiplier,
+            RecoveryUnit::Days => {
+                let days = now.saturating_sub(fairness.updated_at) as f32 / SECONDS_PER_DAY;
+                self.recover(fairness.multiplier, days)
+            }
+        }
+    }
+
+    pub fn record_wins(&self, choices: &mut [Choice], winner_ids: &[u32]) {
+        if !self.enabled {
+            return;
+        }
+
+        let now = now();
+        for choice in choices.iter_mut() {
+            let mut multiplier = self.multiplier(&choice.fairness, now);
+            if self.recovery_unit == RecoveryUnit::Spins {
+                multiplier = self.recover(multiplier, 1.0);
+            }
+            // A choice won by several pointers is lowered once per pointer
+            for _ in winner_ids.iter().filter(|id| **id == choice.id) {
+                multiplier = (multiplier * self.decay).max(constants::MIN_FAIRNESS_MULTIPLIER);
+            }
+            choice.fairness = Fairness {
+                multiplier,
+                updated_at: now,
+            };
+        }
+    }
+
+    pub fn draw_settings(&mut self, ui: &mut egui::Ui, choices: &mut [Choice], enabled: bool) {
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.checkbox(&mut self.enabled, t!("fairness-enabled"));
+
+            ui.add_enabled_ui(self.enabled, |ui| {
+                ui.add(egui::Slider::new(&mut self.decay, 0.05..=0.95).text(t!("fairness-decay")));
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::Slider::new(&mut self.recovery, 0.0..=1.0)
+                            .text(t!("fairness-recovery")),
+                    );
+                    egui::ComboBox::from_id_salt("recovery_unit")
+                        .selected_text(match self.recovery_unit {
+                            RecoveryUnit::Spins => t!("fairness-spin"),
+                            RecoveryUnit::Days => t!("fairness-day"),
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.recovery_unit,
+                                RecoveryUnit::Spins,
+                                t!("fairness-spin"),
+                            );
+                            ui.selectable_value(
+                                &mut self.recovery_unit,
+                                RecoveryUnit::Days,
+                                t!("fairness-day"),
+                            );
+                        });
+                });
+                if ui.button(t!("fairness-reset")).clicked() {
+                    for choice in choices.iter_mut() {
+                        choice.fairness = Fairness::default();
+                    }
+                }
+            });
+        });
+    }
+
+    // Give back `recovery` of the lost weight for every elapsed step
+    fn recover(&self, multiplier: f32, steps: f32) -> f32 {
+        1.0 - (1.0 - multiplier) * (1.0 - self.recovery).powf(steps)
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,356 @@
+use egui::Context;
+
+use super::{constants, i18n::t, wheel::Wheel, WheelChoices};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GeneratorKind {
+    Numbers,
+    Letters,
+    Dice,
+    YesNo,
+    Days,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WeightFormula {
+    Uniform,
+    Linear,
+    BellCurve,
+}
+
+// "Generate choices" window, fills the wheel from a spec
+pub struct Generator {
+    pub open: bool,
+    kind: GeneratorKind,
+    formula: WeightFormula,
+    replace: bool,
+    from: i64,
+    to: i64,
+    step: i64,
+    from_letter: String,
+    to_letter: String,
+    dice_count: u32,
+    dice_sides: u32,
+    maybe: bool,
+    weekdays_only: bool,
+}
+
+impl Generator {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            kind: GeneratorKind::Numbers,
+            formula: WeightFormula::Uniform,
+            replace: true,
+            from: 1,
+            to: 10,
+            step: 1,
+            from_letter: "A".to_owned(),
+            to_letter: "Z".to_owned(),
+            dice_count: 2,
+            dice_sides: 6,
+            maybe: false,
+            weekdays_only: false,
+        }
+    }
+
+    pub fn draw(&mut self, ctx: &Context, wheel_choices: &mut WheelChoices, wheel: &mut Wheel) {
+        let mut open = self.open;
+        let mut generated = false;
+
+        egui::Window::new(t!("generate-choices"))
+            .id(egui::Id::new("generator"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for (kind, text) in [
+                        (GeneratorKind::Numbers, t!("generator-numbers")),
+                        (GeneratorKind::Letters, t!("generator-letters")),
+                        (GeneratorKind::Dice, t!("generator-dice")),
+                        (GeneratorKind::YesNo, t!("generator-yes-no")),
+                        (GeneratorKind::Days, t!("generator-days")),
+                    ] {
+                        ui.selectable_value(&mut self.kind, kind, text);
+                    }
+                });
+                ui.separator();
+
+                self.draw_spec(ui);
+
+                if self.kind != GeneratorKind::Dice {
+                    ui.horizontal(|ui| {
+                        ui.label(t!("generator-weights"));
+                        for (formula, text) in [
+                            (WeightFormula::Uniform, t!("generator-uniform")),
+                            (WeightFormula::Linear, t!("generator-linear")),
+                            (WeightFormula::BellCurve, t!("generator-bell-curve")),
+                        ] {
+                            ui.selectable_value(&mut self.formula, formula, text);
+                        }
+                    });
+                }
+
+                ui.checkbox(&mut self.replace, t!("generator-replace"));
+                ui.separator();
+
+                let choices = self.generate();
+                let room = if self.replace {
+                    constants::MAX_CHOICES
+                } else {
+                    constants::MAX_CHOICES.saturating_sub(wheel_choices.choices.len())
+                };
+                let preview: Vec<&str> = choices
+                    .iter()
+                    .take(constants::GENERATOR_PREVIEW_SIZE)
+                    .map(|(label, _)| label.as_str())
+                    .collect();
+                ui.label(t!(
+                    "generator-preview",
+                    count = choices.len(),
+                    preview = preview.join(", ")
+                ));
+                if choices.len() > room {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        t!("generator-overflow", room = room),
+                    );
+                }
+                if raises_weights(&choices) {
+                    ui.colored_label(ui.visuals().warn_fg_color, t!("generator-raised-weights"));
+                }
+
+                if ui
+                    .add_enabled(
+                        room > 0 && !choices.is_empty() && !wheel.spinning,
+                        egui::Button::new(t!("generator-generate")),
+                    )
+                    .clicked()
+                {
+                    wheel_choices.add_generated(choices, self.replace, wheel);
+                    generated = true;
+                }
+            });
+
+        self.open = open && !generated;
+    }
+
+    fn draw_spec(&mut self, ui: &mut egui::Ui) {
+        match self.kind {
+            GeneratorKind::Numbers => {
+                ui.horizontal(|ui| {
+                    ui.label(t!("generator-from"));
+                    ui.add(egui::DragValue::new(&mut self.from));
+                    ui.label(t!("generator-to"));
+                    ui.add(egui::DragValue::new(&mut self.to));
+                    ui.label(t!("generator-step"));
+                    ui.add(egui::DragValue::new(&mut self.step).range(1..=i64::MAX));
+                });
+            }
+            GeneratorKind::Letters => {
+                ui.horizontal(|ui| {
+                    ui.label(t!("generator-from"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.from_letter)
+                            .char_limit(1)
+                            .desired_width(ui.spacing().interact_size.x / 2.0),
+                    );
+                    ui.label(t!("generator-to"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.to_letter)
+                            .char_limit(1)
+                            .desired_width(ui.spacing().interact_size.x / 2.0),
+                    );
+                });
+            }
+            GeneratorKind::Dice => {
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.dice_count).range(1..=20));
+                    ui.label("d");
+                    ui.add(egui::DragValue::new(&mut self.dice_sides).range(2..=100));
+                });
+                ui.weak(t!("generator-dice-odds"));
+            }
+            GeneratorKind::YesNo => {
+                ui.checkbox(&mut self.maybe, t!("generator-add-maybe"));
+            }
+            GeneratorKind::Days => {
+                ui.checkbox(&mut self.weekdays_only, t!("generator-weekdays"));
+            }
+        }
+    }
+
+    // Labels with their weight
+    fn generate(&self) -> Vec<(String, f64)> {
+        let labels: Vec<String> = match self.kind {
+            GeneratorKind::Numbers => {
+                let (from, to) = (self.from.min(self.to), self.from.max(self.to));
+                // A step past the range of usize, on 32-bit targets, only
+                // keeps the first number like any step longer than the range
+                let step = usize::try_from(self.step.max(1)).unwrap_or(usize::MAX);
+                (from..=to)
+                    .step_by(step)
+                    .take(constants::MAX_GENERATED_CHOICES)
+                    .map(|number| number.to_string())
+                    .collect()
+            }
+            GeneratorKind::Letters => {
+                let (Some(from), Some(to)) = (
+                    self.from_letter.chars().next(),
+                    self.to_letter.chars().next(),
+                ) else {
+                    return Vec::new();
+                };
+                (from.min(to)..=from.max(to))
+                    .take(constants::MAX_GENERATED_CHOICES)
+                    .map(|letter| letter.to_string())
+                    .collect()
+            }
+            GeneratorKind::Dice => return self.dice_sums(),
+            GeneratorKind::YesNo => {
+                let mut labels = vec![t!("generator-yes"), t!("generator-no")];
+                if self.maybe {
+                    labels.push(t!("generator-maybe"));
+                }
+                labels
+            }
+            GeneratorKind::Days => {
+                let days = [
+                    t!("day-monday"),
+                    t!("day-tuesday"),
+                    t!("day-wednesday"),
+                    t!("day-thursday"),
+                    t!("day-friday"),
+                    t!("day-saturday"),
+                    t!("day-sunday"),
+                ];
+                let count = if self.weekdays_only { 5 } else { days.len() };
+                days[..count].to_vec()
+            }
+        };
+
+        let count = labels.len();
+        labels
+            .into_iter()
+            .enumerate()
+            .map(|(i, label)| (label, self.formula.weight(i, count)))
+            .collect()
+    }
+
+    // Every sum of the dice, weighted by the number of rolls giving it
+    fn dice_sums(&self) -> Vec<(String, f64)> {
+        let sides = self.dice_sides.max(2) as usize;
+        // ways[s] = number of rolls summing to s + number of dice
+        let mut ways: Vec<f64> = vec![1.0];
+        for _ in 0..self.dice_count.max(1) {
+            let mut next = vec![0.0; ways.len() + sides - 1];
+            for (sum, count) in ways.iter().enumerate() {
+                for face in 0..sides {
+                    next[sum + face] += count;
+                }
+            }
+            ways = next;
+        }
+
+        // Many dice have counts past the largest weight, scale them all down
+        // instead of clamping the middle sums to the same weight
+        let largest = ways.iter().copied().fold(1.0, f64::max);
+        let scale = (constants::MAX_SEGMENT_WEIGHT / largest).min(1.0);
+        ways.into_iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let label = (i + self.dice_count.max(1) as usize).to_string();
+                (label, count * scale)
+            })
+            .collect()
+    }
+}
+
+// Whether some weights are below the smallest one a choice can have, the wheel
+// would then give them better odds than the formula
+fn raises_weights(choices: &[(String, f64)]) -> bool {
+    choices
+        .iter()
+        .any(|(_, weight)| *weight < constants::MIN_SEGMENT_WEIGHT)
+}
+
+impl WeightFormula {
+    fn weight(&self, index: usize, count: usize) -> f64 {
+        match self {
+            WeightFormula::Uniform => 1.0,
+            WeightFormula::Linear => (index + 1) as f64,
+            WeightFormula::BellCurve => {
+                let middle = (count as f64 - 1.0) / 2.0;
+                let deviation = (count as f64 / 4.0).max(1.0);
+                let weight = (-(index as f64 - middle).powi(2) / (2.0 * deviation.powi(2))).exp();
+                // Keep two significant decimals so the weights stay readable
+                ((weight * 100.0).round() / 100.0).max(constants::MIN_SEGMENT_WEIGHT)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{raises_weights, Generator};
+    use crate::app::constants;
+
+    fn dice(count: u32, sides: u32) -> Vec<(String, f64)> {
+        let mut generator = Generator::new();
+        generator.dice_count = count;
+        generator.dice_sides = sides;
+        generator.dice_sums()
+    }
+
+    #[test]
+    fn huge_step_keeps_the_first_number() {
+        let mut generator = Generator::new();
+        generator.from = -5;
+        generator.to = 5;
+        generator.step = i64::MAX;
+        let labels: Vec<String> = generator
+            .generate()
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect();
+        assert_eq!(labels, ["-5"]);
+    }
+
+    #[test]
+    fn two_dice_weights() {
+        let sums = dice(2, 6);
+        let labels: Vec<&str> = sums.iter().map(|(label, _)| label.as_str()).collect();
+        let weights: Vec<f64> = sums.iter().map(|(_, weight)| *weight).collect();
+        assert_eq!(
+            labels,
+            ["2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12"]
+        );
+        assert_eq!(
+            weights,
+            [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn many_dice_keep_their_shape() {
+        let sums = dice(20, 100);
+        let weights: Vec<f64> = sums.iter().map(|(_, weight)| *weight).collect();
+        let middle = weights.len() / 2;
+        assert_eq!(weights[middle], constants::MAX_SEGMENT_WEIGHT);
+        // Sums next to the middle are less likely than the middle one
+        assert!(weights[middle - 50] < weights[middle - 1]);
+        assert!(weights[middle - 1] < weights[middle]);
+        assert!(weights
+            .iter()
+            .all(|weight| *weight <= constants::MAX_SEGMENT_WEIGHT));
+    }
+
+    #[test]
+    fn raised_weights_are_reported() {
+        assert!(!raises_weights(&dice(2, 6)));
+        assert!(!raises_weights(&dice(10, 6)));
+        // The extreme sums are far too unlikely next to the middle one
+        assert!(raises_weights(&dice(20, 100)));
+    }
+}
@@ -0,0 +1,235 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    OnceLock,
+};
+
+pub use fluent_bundle::FluentValue;
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use serde::{Deserialize, Serialize};
+
+pub const LANGUAGE_KEY: &str = "language";
+
+// Translated message, with optional `name = value` arguments
+macro_rules! t {
+    ($id:expr) => {
+        $crate::app::i18n::tr($id, &[])
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::app::i18n::tr(
+            $id,
+            &[$((stringify!($name), $crate::app::i18n::FluentValue::from($value))),+],
+        )
+    };
+}
+pub(crate) use t;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    English,
+    French,
+}
+
+// Index in `Language::ALL` of the language in use
+static CURRENT: AtomicUsize = AtomicUsize::new(usize::MAX);
+static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::French];
+
+    fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::French => "fr",
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../../locales/en.ftl"),
+            Language::French => include_str!("../../locales/fr.ftl"),
+        }
+    }
+
+    // Always written in the language itself
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+        }
+    }
+
+    // Language of the system, English when it isn't translated
+    pub fn system() -> Language {
+        let locale = sys_locale::get_locale().unwrap_or_default();
+        Language::ALL
+            .into_iter()
+            .find(|language| {
+                locale
+                    .split(['-', '_'])
+                    .next()
+                    .is_some_and(|code| code.eq_ignore_ascii_case(language.code()))
+            })
+            .unwrap_or(Language::English)
+    }
+
+    fn index(&self) -> usize {
+        Language::ALL
+            .iter()
+            .position(|language| language == self)
+            .unwrap_or(0)
+    }
+
+    fn bundle(&self) -> FluentBundle<FluentResource> {
+        let language_id = self.code().parse().expect("valid language code");
+        let mut bundle = FluentBundle::new_concurrent(vec![language_id]);
+        // Isolation marks around arguments show up as boxes in egui
+        bundle.set_use_isolating(false);
+        let resource = FluentResource::try_new(self.source().to_owned())
+            .unwrap_or_else(|(resource, _)| resource);
+        let _ = bundle.add_resource(resource);
+        bundle
+    }
+}
+
+// `None` follows the language of the system
+pub fn set_language(language: Option<Language>) {
+    let language = language.unwrap_or_else(Language::system);
+    CURRENT.store(language.index(), Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        usize::MAX => {
+            let language = Language::system();
+            CURRENT.store(language.index(), Ordering::Relaxed);
+            language
+        }
+        index => Language::ALL[index],
+    }
+}
+
+// Message `id` in the current language, in English if it isn't translated,
+// or the id itself so that missing messages are easy to spot
+pub fn tr(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let bundles = BUNDLES.get_or_init(|| Language::ALL.iter().map(Language::bundle).collect());
+    let args = (!args.is_empty()).then(|| {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        fluent_args
+    });
+
+    for bundle in [&bundles[language().index()], &bundles[0]] {
+        if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) {
+            let mut errors = Vec::new();
+            return bundle
+                .format_pattern(pattern, args.as_ref(), &mut errors)
+                .into_owned();
+        }
+    }
+    id.to_owned()
+}
+
+// Language picker, "System" follows the language of the system
+pub fn draw_selector(ui: &mut egui::Ui, setting: &mut Option<Language>) {
+    let system = format!("{} ({})", t!("language-system"), Language::system().name());
+    let selected = setting.map_or(system.clone(), |language| language.name().to_owned());
+    let previous = *setting;
+    egui::ComboBox::from_id_salt("language")
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(setting, None, system);
+            for language in Language::ALL {
+                ui.selectable_value(setting, Some(language), language.name());
+            }
+        });
+    if *setting != previous {
+        set_language(*setting);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, fs, path::Path};
+
+    use fluent_bundle::{FluentResource, FluentValue};
+    use fluent_syntax::ast::Entry;
+
+    use super::Language;
+
+    fn message_ids(language: Language) -> BTreeSet<String> {
+        let resource = FluentResource::try_new(language.source().to_owned())
+            .unwrap_or_else(|(_, errors)| panic!("{language:?} catalog: {errors:?}"));
+        resource
+            .entries()
+            .filter_map(|entry| match entry {
+                Entry::Message(message) => Some(message.id.name.to_owned()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Ids passed to `t!` in the sources
+    fn used_ids(directory: &Path, ids: &mut BTreeSet<String>) {
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                used_ids(&path, ids);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                let source = fs::read_to_string(&path).unwrap();
+                for part in source.split("t!(").skip(1) {
+                    let part = part.trim_start();
+                    if let Some(rest) = part.strip_prefix('"') {
+                        let id: String = rest
+                            .chars()
+                            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+                            .collect();
+                        if !id.is_empty() && rest[id.len()..].starts_with('"') {
+                            ids.insert(id);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn catalogs_have_the_same_messages() {
+        let english = message_ids(Language::English);
+        for language in Language::ALL {
+            let ids = message_ids(language);
+            let missing: Vec<_> = english.difference(&ids).collect();
+            let unknown: Vec<_> = ids.difference(&english).collect();
+            assert!(missing.is_empty(), "missing in {language:?}: {missing:?}");
+            assert!(unknown.is_empty(), "only in {language:?}: {unknown:?}");
+        }
+    }
+
+    #[test]
+    fn used_messages_exist() {
+        let mut used = BTreeSet::new();
+        used_ids(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut used,
+        );
+        assert!(!used.is_empty());
+        let english = message_ids(Language::English);
+        let missing: Vec<_> = used.difference(&english).collect();
+        assert!(missing.is_empty(), "missing messages: {missing:?}");
+    }
+
+    #[test]
+    fn plurals_follow_the_language() {
+        let bundle = Language::French.bundle();
+        let message = bundle.get_message("winner-remove").unwrap();
+        let mut args = fluent_bundle::FluentArgs::new();
+        args.set("count", FluentValue::from(1));
+        let mut errors = Vec::new();
+        let one = bundle.format_pattern(message.value().unwrap(), Some(&args), &mut errors);
+        args.set("count", FluentValue::from(3));
+        let other = bundle.format_pattern(message.value().unwrap(), Some(&args), &mut errors);
+        assert_ne!(one, other);
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+}
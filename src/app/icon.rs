@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use egui::{load::SizeHint, load::TexturePoll, Context, TextureOptions};
+use serde::{Deserialize, Serialize};
+use twemoji_assets::svg::SvgTwemojiAsset;
+
+use super::{constants, i18n::t};
+
+// Picture drawn in a segment, saved with the wheel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Icon {
+    Image {
+        // Extension of the original file, tells the loaders how to decode it
+        format: String,
+        // Hash of the bytes, names the picture in the loaders' caches
+        key: u64,
+        #[serde(with = "base64_bytes")]
+        bytes: Arc<[u8]>,
+    },
+    // Drawn from the Twemoji pictures since egui has no color font
+    Emoji(String),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+const FORMATS: [&str; 4] = ["png", "jpg", "jpeg", "svg"];
+
+impl Icon {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_file(path: &str) -> Result<Icon, String> {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let format = std::path::Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase)
+            .filter(|extension| FORMATS.contains(&extension.as_str()))
+            .ok_or_else(|| t!("icon-unsupported-format", path = path))?;
+        let bytes = std::fs::read(path)
+            .map_err(|error| t!("icon-read-error", path = path, error = error.to_string()))?;
+        if bytes.len() > constants::MAX_ICON_BYTES {
+            return Err(t!(
+                "icon-too-large",
+                path = path,
+                max = constants::MAX_ICON_BYTES / 1024
+            ));
+        }
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        Ok(Icon::Image {
+            format,
+            key: hasher.finish(),
+            bytes: bytes.into(),
+        })
+    }
+
+    pub fn from_emoji(text: &str) -> Result<Icon, String> {
+        let emoji = text.trim();
+        if Icon::emoji_asset(emoji).is_none() {
+            return Err(t!("icon-unknown-emoji", emoji = emoji));
+        }
+        Ok(Icon::Emoji(emoji.to_owned()))
+    }
+
+    // Twemoji leaves out most variation selectors
+    fn emoji_asset(emoji: &str) -> Option<&'static SvgTwemojiAsset> {
+        SvgTwemojiAsset::from_emoji(emoji)
+            .or_else(|| SvgTwemojiAsset::from_emoji(&emoji.replace('\u{fe0f}', "")))
+    }
+
+    // Same uri for the same picture, so that the loaders load it once
+    fn uri(&self) -> String {
+        match self {
+            Icon::Image { format, key, .. } => format!("bytes://icon-{key:016x}.{format}"),
+            Icon::Emoji(emoji) => format!("bytes://emoji-{emoji}.svg"),
+        }
+    }
+
+    fn bytes(&self) -> egui::load::Bytes {
+        match self {
+            Icon::Image { bytes, .. } => bytes.clone().into(),
+            Icon::Emoji(emoji) => Icon::emoji_asset(emoji)
+                .map(|asset| asset.as_bytes())
+                .unwrap_or_default()
+                .into(),
+        }
+    }
+
+    // Widget showing the picture, for the lists and dialogs
+    pub fn image(&self) -> egui::Image<'static> {
+        egui::Image::from_bytes(self.uri(), self.bytes())
+    }
+
+    // Texture to paint in the wheel, `None` until it is loaded or when the
+    // file can't be decoded
+    pub fn texture(&self, ctx: &Context) -> Option<egui::load::SizedTexture> {
+        let uri = self.uri();
+        ctx.include_bytes(uri.clone(), self.bytes());
+        let size = constants::ICON_TEXTURE_SIZE;
+        match ctx.try_load_texture(&uri, TextureOptions::LINEAR, SizeHint::Size(size, size)) {
+            Ok(TexturePoll::Ready { texture }) => Some(texture),
+            _ => None,
+        }
+    }
+}
+
+// Pictures are kept as base64 text, much smaller than a list of numbers
+mod base64_bytes {
+    use std::sync::Arc;
+
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &Arc<[u8]>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<[u8]>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        STANDARD
+            .decode(text)
+            .map(Into::into)
+            .map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,277 @@
+use eframe::egui::{self, Context, Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+
+use super::i18n::t;
+
+pub const KEYMAP_KEY: &str = "keymap";
+
+// Everything that can be done from the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    Spin,
+    FocusAdd,
+    AddChoice,
+    PreviousChoice,
+    NextChoice,
+    IncreaseWeight,
+    DecreaseWeight,
+    Rename,
+    Remove,
+    CloseDialog,
+    ShowShortcuts,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Binding {
+    action: Action,
+    shortcut: KeyboardShortcut,
+}
+
+// Shortcut of each action, saved for the whole application
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+    // Action waiting for its new shortcut in the settings
+    #[serde(skip)]
+    recording: Option<Action>,
+    #[serde(skip)]
+    pub cheat_sheet_open: bool,
+}
+
+impl Action {
+    pub const ALL: [Action; 11] = [
+        Action::Spin,
+        Action::FocusAdd,
+        Action::AddChoice,
+        Action::PreviousChoice,
+        Action::NextChoice,
+        Action::IncreaseWeight,
+        Action::DecreaseWeight,
+        Action::Rename,
+        Action::Remove,
+        Action::CloseDialog,
+        Action::ShowShortcuts,
+    ];
+
+    fn description(&self) -> String {
+        match self {
+            Action::Spin => t!("action-spin"),
+            Action::FocusAdd => t!("action-focus-add"),
+            Action::AddChoice => t!("action-add-choice"),
+            Action::PreviousChoice => t!("action-previous-choice"),
+            Action::NextChoice => t!("action-next-choice"),
+            Action::IncreaseWeight => t!("action-increase-weight"),
+            Action::DecreaseWeight => t!("action-decrease-weight"),
+            Action::Rename => t!("action-rename"),
+            Action::Remove => t!("action-remove"),
+            Action::CloseDialog => t!("action-close-dialog"),
+            Action::ShowShortcuts => t!("action-show-shortcuts"),
+        }
+    }
+
+    fn default_shortcut(&self) -> KeyboardShortcut {
+        let (modifiers, key) = match self {
+            Action::Spin => (Modifiers::NONE, Key::Space),
+            Action::FocusAdd => (Modifiers::COMMAND, Key::N),
+            Action::AddChoice => (Modifiers::NONE, Key::Enter),
+            Action::PreviousChoice => (Modifiers::NONE, Key::ArrowUp),
+            Action::NextChoice => (Modifiers::NONE, Key::ArrowDown),
+            Action::IncreaseWeight => (Modifiers::NONE, Key::Plus),
+            Action::DecreaseWeight => (Modifiers::NONE, Key::Minus),
+            Action::Rename => (Modifiers::NONE, Key::F2),
+            Action::Remove => (Modifiers::NONE, Key::Delete),
+            Action::CloseDialog => (Modifiers::NONE, Key::Escape),
+            Action::ShowShortcuts => (Modifiers::NONE, Key::F1),
+        };
+        KeyboardShortcut::new(modifiers, key)
+    }
+
+    // Most shortcuts are plain keys, they would get in the way of typing
+    fn works_while_typing(&self) -> bool {
+        matches!(
+            self,
+            Action::FocusAdd | Action::AddChoice | Action::CloseDialog | Action::ShowShortcuts
+        )
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .iter()
+                .map(|action| Binding {
+                    action: *action,
+                    shortcut: action.default_shortcut(),
+                })
+                .collect(),
+            recording: None,
+            cheat_sheet_open: false,
+        }
+    }
+}
+
+impl Keymap {
+    pub fn shortcut(&self, action: Action) -> KeyboardShortcut {
+        self.bindings
+            .iter()
+            .find(|binding| binding.action == action)
+            .map(|binding| binding.shortcut)
+            .unwrap_or_else(|| action.default_shortcut())
+    }
+
+    // Consumes the shortcut of the action if it was pressed
+    pub fn pressed(&self, ctx: &Context, action: Action) -> bool {
+        if self.recording.is_some() {
+            return false;
+        }
+        if !action.works_while_typing() && ctx.wants_keyboard_input() {
+            return false;
+        }
+        let shortcut = self.shortcut(action);
+        ctx.input_mut(|i| i.consume_shortcut(&shortcut))
+    }
+
+    // Actions triggered this frame, adding a choice is left to the input box
+    pub fn triggered(&self, ctx: &Context) -> Vec<Action> {
+        Action::ALL
+            .into_iter()
+            .filter(|action| *action != Action::AddChoice && self.pressed(ctx, *action))
+            .collect()
+    }
+
+    // An action taking the shortcut of another one gives it its own, two
+    // actions never share a shortcut
+    fn set_shortcut(&mut self, action: Action, shortcut: KeyboardShortcut) {
+        let previous = self.shortcut(action);
+        if let Some(other) = Action::ALL
+            .into_iter()
+            .find(|other| *other != action && same_shortcut(&self.shortcut(*other), &shortcut))
+        {
+            self.bind(other, previous);
+        }
+        self.bind(action, shortcut);
+    }
+
+    fn bind(&mut self, action: Action, shortcut: KeyboardShortcut) {
+        match self
+            .bindings
+            .iter_mut()
+            .find(|binding| binding.action == action)
+        {
+            Some(binding) => binding.shortcut = shortcut,
+            None => self.bindings.push(Binding { action, shortcut }),
+        }
+    }
+
+    // Lists the shortcuts, clicking one waits for its new key
+    pub fn draw_settings(&mut self, ui: &mut egui::Ui) {
+        if let Some(action) = self.recording {
+            let pressed = ui.input(|i| {
+                i.events.iter().find_map(|event| match event {
+                    egui::Event::Key {
+                        key,
+                        pressed: true,
+                        modifiers,
+                        ..
+                    } => Some(KeyboardShortcut::new(*modifiers, *key)),
+                    _ => None,
+                })
+            });
+            // Escape gives up, it can't be recorded
+            match pressed {
+                Some(shortcut) if shortcut.logical_key == Key::Escape => self.recording = None,
+                Some(shortcut) => {
+                    self.set_shortcut(action, shortcut);
+                    self.recording = None;
+                }
+                None => {}
+            }
+        }
+
+        egui::Grid::new("keymap").num_columns(2).show(ui, |ui| {
+            for action in Action::ALL {
+                ui.label(action.description());
+                let text = if self.recording == Some(action) {
+                    t!("keymap-press-key")
+                } else {
+                    ui.ctx().format_shortcut(&self.shortcut(action))
+                };
+                if ui
+                    .selectable_label(self.recording == Some(action), text)
+                    .on_hover_text(t!("keymap-record-tooltip"))
+                    .clicked()
+                {
+                    self.recording = Some(action);
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button(t!("keymap-reset")).clicked() {
+                *self = Self::default();
+            }
+            if ui.button(t!("keymap-cheat-sheet")).clicked() {
+                self.cheat_sheet_open = true;
+            }
+        });
+    }
+
+    pub fn draw_cheat_sheet(&mut self, ctx: &Context) {
+        let mut open = self.cheat_sheet_open;
+        egui::Window::new(t!("section-keyboard-shortcuts"))
+            .id(egui::Id::new("cheat_sheet"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                egui::Grid::new("cheat_sheet")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for action in Action::ALL {
+                            ui.strong(ctx.format_shortcut(&self.shortcut(action)));
+                            ui.label(action.description());
+                            ui.end_row();
+                        }
+                    });
+            });
+        self.cheat_sheet_open = open;
+    }
+}
+
+// Ctrl and Command are the same modifier on Windows and Linux
+fn same_shortcut(a: &KeyboardShortcut, b: &KeyboardShortcut) -> bool {
+    a.logical_key == b.logical_key
+        && (a.modifiers.matches_exact(b.modifiers) || b.modifiers.matches_exact(a.modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::egui::{Key, KeyboardShortcut, Modifiers};
+
+    use super::{Action, Keymap};
+
+    #[test]
+    fn taken_shortcut_is_swapped() {
+        let mut keymap = Keymap::default();
+        let space = keymap.shortcut(Action::Spin);
+        let f2 = keymap.shortcut(Action::Rename);
+
+        keymap.set_shortcut(Action::Spin, f2);
+        assert_eq!(keymap.shortcut(Action::Spin), f2);
+        assert_eq!(keymap.shortcut(Action::Rename), space);
+
+        // Ctrl is Command outside of macOS
+        let ctrl_n = KeyboardShortcut::new(Modifiers::CTRL | Modifiers::COMMAND, Key::N);
+        keymap.set_shortcut(Action::Remove, ctrl_n);
+        assert_eq!(keymap.shortcut(Action::Remove), ctrl_n);
+        assert_eq!(
+            keymap.shortcut(Action::FocusAdd),
+            KeyboardShortcut::new(Modifiers::NONE, Key::Delete)
+        );
+    }
+}
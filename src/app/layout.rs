@@ -0,0 +1,270 @@
+use eframe::egui::{self, FontId, Rect};
+
+use super::{
+    accessibility, constants, i18n, i18n::t, keymap::Action, App, WheelChoices, ADD_INPUT_ID,
+};
+
+// Side by side when there is room, the editor is a drawer under the wheel
+// on narrow or portrait windows
+#[derive(Clone, Copy, PartialEq)]
+pub enum LayoutMode {
+    Wide,
+    Narrow,
+}
+
+impl LayoutMode {
+    pub fn of(screen: Rect) -> Self {
+        if screen.width() < constants::NARROW_LAYOUT_WIDTH || screen.height() > screen.width() {
+            LayoutMode::Narrow
+        } else {
+            LayoutMode::Wide
+        }
+    }
+}
+
+// Titles shrink with the window, down to a readable size
+pub fn title_size(width: f32) -> f32 {
+    (constants::TITLE_SIZE * width / constants::NARROW_LAYOUT_WIDTH).clamp(
+        constants::TITLE_SIZE * constants::MIN_FONT_SCALE,
+        constants::TITLE_SIZE,
+    )
+}
+
+// Largest wheel fitting in `rect`, with room left for the pointers
+pub fn wheel_radius(rect: Rect) -> f32 {
+    rect.width().min(rect.height()) / 2.0 * constants::WHEEL_FILL
+}
+
+impl App {
+    // Choices and settings
+    pub(super) fn draw_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        actions: &[Action],
+    ) {
+        // Breadcrumb
+        if !self.breadcrumb.is_empty() {
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!self.wheel.spinning, egui::Button::new(t!("back-button")))
+                    .clicked()
+                {
+                    self.close_sub_wheel();
+                }
+                for crumb in self.breadcrumb.iter() {
+                    ui.weak(&crumb.parent.name);
+                    ui.label(format!("› {} ›", crumb.label));
+                }
+                ui.strong(&self.wheel_choices.name);
+            });
+            ui.add_space(constants::SPACER_AMOUNT / 2.0);
+        }
+
+        // Add
+
+        ui.horizontal(|ui| {
+            let button_width: f32 =
+                ui.spacing().interact_size.x * 2.0 + ui.spacing().item_spacing.x;
+            let input_width: f32 = ui.available_width() - button_width;
+
+            egui::ScrollArea::vertical()
+                .max_height(ui.spacing().interact_size.y * 3.0)
+                .show(ui, |ui: &mut egui::Ui| {
+                    let can_type = self.can_type_segment();
+                    let response = ui.add_enabled(
+                        can_type,
+                        egui::TextEdit::multiline(&mut self.input_text)
+                            .hint_text(if can_type {
+                                t!("add-choice-hint")
+                            } else {
+                                t!("max-choices-reached", max = constants::MAX_CHOICES)
+                            })
+                            .char_limit(constants::MAX_INPUT_SIZE)
+                            .id(egui::Id::new(ADD_INPUT_ID))
+                            .desired_width(input_width)
+                            .desired_rows(1),
+                    );
+                    if response.has_focus() && self.keymap.pressed(ctx, Action::AddChoice) {
+                        self.add_segment_ui();
+                    }
+                });
+
+            if ui
+                .add_enabled(self.can_add_segment(), egui::Button::new(t!("add-button")))
+                .clicked()
+            {
+                self.add_segment_ui();
+            }
+            let generate_response = ui
+                .add_enabled(!self.wheel.spinning, egui::Button::new("🎲"))
+                .on_hover_text(t!("generate-choices"));
+            accessibility::set_name(&generate_response, t!("generate-choices"));
+            if generate_response.clicked() {
+                self.generator.open = true;
+            }
+        });
+
+        ui.add_space(constants::SPACER_AMOUNT);
+
+        // Choices, the shortcuts are for the dialog in front of them if any
+        let list_actions = if self.dialog_open(ctx) { &[] } else { actions };
+        self.choices_ui.draw(
+            ui,
+            ctx,
+            &mut self.wheel_choices,
+            &mut self.wheel,
+            &self.library.names(),
+            list_actions,
+        );
+
+        ui.add_space(constants::SPACER_AMOUNT / 2.0);
+        egui::ScrollArea::vertical()
+            .id_salt("settings")
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                // Saved wheels
+                egui::CollapsingHeader::new(t!("section-saved-wheels"))
+                    .id_salt("saved-wheels")
+                    .show(ui, |ui| {
+                        let enabled = !self.wheel.spinning && self.breadcrumb.is_empty();
+                        if let Some(name) =
+                            self.library
+                                .draw(ui, &mut self.wheel_choices, enabled, actions)
+                        {
+                            self.open_wheel(&name);
+                        }
+                    });
+
+                // Results
+                egui::CollapsingHeader::new(t!("section-results"))
+                    .id_salt("results")
+                    .show(ui, |ui| {
+                        self.results.draw(ui);
+                    });
+
+                // Fair rotation
+                egui::CollapsingHeader::new(t!("section-fair-rotation"))
+                    .id_salt("fair-rotation")
+                    .show(ui, |ui| {
+                        let WheelChoices {
+                            fair_rotation,
+                            choices,
+                            ..
+                        } = &mut self.wheel_choices;
+                        fair_rotation.draw_settings(ui, choices, !self.wheel.spinning);
+                    });
+
+                // Spin settings
+                egui::CollapsingHeader::new(t!("section-spin-settings"))
+                    .id_salt("spin-settings")
+                    .show(ui, |ui| {
+                        self.wheel_choices
+                            .spin_settings
+                            .draw(ui, !self.wheel.spinning);
+                        if ui.button(t!("verify-fairness")).clicked() {
+                            self.fairness_dialog.open = true;
+                        }
+                    });
+
+                // Sound
+                egui::CollapsingHeader::new(t!("section-sound"))
+                    .id_salt("sound")
+                    .show(ui, |ui| {
+                        self.audio.draw_settings(ui);
+                    });
+
+                // Keyboard shortcuts
+                egui::CollapsingHeader::new(t!("section-keyboard-shortcuts"))
+                    .id_salt("keyboard-shortcuts")
+                    .show(ui, |ui| {
+                        self.keymap.draw_settings(ui);
+                    });
+
+                // Celebration
+                egui::CollapsingHeader::new(t!("section-celebration"))
+                    .id_salt("celebration")
+                    .show(ui, |ui| {
+                        self.celebration.draw_settings(ui);
+                    });
+
+                // Spin physics
+                egui::CollapsingHeader::new(t!("section-spin-physics"))
+                    .id_salt("spin-physics")
+                    .show(ui, |ui| {
+                        self.wheel_choices
+                            .spin_settings
+                            .physics
+                            .draw(ui, !self.wheel.spinning);
+                    });
+
+                // Theme
+                egui::CollapsingHeader::new(t!("section-theme"))
+                    .id_salt("theme")
+                    .show(ui, |ui| {
+                        self.appearance.draw_settings(ui);
+                    });
+
+                // Language
+                egui::CollapsingHeader::new(t!("section-language"))
+                    .id_salt("language")
+                    .show(ui, |ui| {
+                        i18n::draw_selector(ui, &mut self.language);
+                    });
+            });
+    }
+
+    // Spin button, with the drawer toggle on narrow windows
+    pub(super) fn draw_spin_bar(&mut self, ui: &mut egui::Ui, mode: LayoutMode) {
+        let title_size = title_size(ui.ctx().screen_rect().width());
+        let spin_button = egui::Button::new(
+            egui::RichText::new(t!("spin-the-wheel")).font(FontId::proportional(title_size)),
+        );
+        let can_spin = self.can_spin();
+
+        match mode {
+            LayoutMode::Wide => {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(constants::SPACER_AMOUNT);
+                    if ui.add_enabled(can_spin, spin_button).clicked() {
+                        self.wheel.start_spin(&self.wheel_choices);
+                    }
+                    ui.add_space(constants::SPACER_AMOUNT / 2.0);
+                    ui.horizontal(|ui| {
+                        self.draw_secondary_buttons(ui, title_size);
+                    });
+                    ui.add_space(constants::SPACER_AMOUNT / 2.0);
+                });
+            }
+            LayoutMode::Narrow => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.toggle_value(&mut self.drawer_open, t!("drawer-toggle"))
+                        .on_hover_text(t!("drawer-toggle-tooltip"));
+                    if ui.add_enabled(can_spin, spin_button).clicked() {
+                        self.wheel.start_spin(&self.wheel_choices);
+                    }
+                    self.draw_secondary_buttons(ui, title_size);
+                });
+            }
+        }
+    }
+
+    fn draw_secondary_buttons(&mut self, ui: &mut egui::Ui, title_size: f32) {
+        // The spinning wheel keeps its choices
+        if ui
+            .add_enabled(
+                !self.wheel.spinning,
+                egui::Button::new(
+                    egui::RichText::new(t!("clear-the-wheel"))
+                        .font(FontId::proportional(title_size / 2.0)),
+                ),
+            )
+            .clicked()
+        {
+            self.wheel_choices.clear();
+            self.wheel.clear();
+        }
+        ui.toggle_value(&mut self.presenter.open, t!("presenter-mode"))
+            .on_hover_text(t!("presenter-mode-tooltip"));
+    }
+}
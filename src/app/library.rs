@@ -0,0 +1,208 @@
+use egui_modal::Modal;
+use serde::{Deserialize, Serialize};
+
+use super::{accessibility, constants, fairness, i18n::t, keymap::Action, WheelChoices};
+
+pub const LIBRARY_KEY: &str = "library";
+pub const RESULTS_KEY: &str = "results";
+
+// Wheels saved under a name, a choice can open one of them
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WheelLibrary {
+    wheels: Vec<WheelChoices>,
+    // Waiting for the user to confirm it
+    #[serde(skip)]
+    pending: Option<Pending>,
+}
+
+// Actions losing a wheel, confirmed first
+enum Pending {
+    // Replace the saved wheel with the same name as the current one
+    Overwrite,
+    // Replace the current wheel with a saved one
+    Open(String),
+}
+
+// Outcomes of the previous spins, newest first
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResultHistory {
+    results: Vec<Outcome>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Outcome {
+    text: String,
+    time: u64,
+}
+
+// Wheel left to spin one of its choices' sub-wheel
+pub struct Crumb {
+    pub parent: WheelChoices,
+    pub label: String,
+}
+
+impl WheelLibrary {
+    pub fn names(&self) -> Vec<String> {
+        self.wheels.iter().map(|wheel| wheel.name.clone()).collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<WheelChoices> {
+        self.wheels
+            .iter()
+            .find(|wheel| wheel.name == name)
+            .map(WheelChoices::snapshot)
+    }
+
+    // Replaces the saved wheel with the same name
+    pub fn save(&mut self, wheel_choices: &WheelChoices) {
+        let wheel = wheel_choices.snapshot();
+        match self
+            .wheels
+            .iter_mut()
+            .find(|saved| saved.name == wheel.name)
+        {
+            Some(saved) => *saved = wheel,
+            None => self.wheels.push(wheel),
+        }
+    }
+
+    // Returns the name of the wheel to open
+    pub fn draw(
+        &mut self,
+        ui: &mut egui::Ui,
+        current: &mut WheelChoices,
+        enabled: bool,
+        actions: &[Action],
+    ) -> Option<String> {
+        let mut wheel_to_open: Option<String> = None;
+        let mut wheel_to_delete: Option<usize> = None;
+        let modal = Modal::new(ui.ctx(), "library_dialog");
+        if modal.is_open() && actions.contains(&Action::CloseDialog) {
+            self.pending = None;
+            modal.close();
+        }
+
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut current.name)
+                        .hint_text(t!("library-name-hint"))
+                        .char_limit(constants::MAX_RANGE_TEXT_LENGTH * 2),
+                );
+                let name = current.name.trim().to_owned();
+                if ui
+                    .add_enabled(!name.is_empty(), egui::Button::new(t!("library-save")))
+                    .clicked()
+                {
+                    current.name = name;
+                    if self.wheels.iter().any(|wheel| wheel.name == current.name) {
+                        self.pending = Some(Pending::Overwrite);
+                        modal.open();
+                    } else {
+                        self.save(current);
+                    }
+                }
+            });
+
+            for (i, wheel) in self.wheels.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} ({})", wheel.name, wheel.choices.len()));
+                    if ui.button(t!("library-open")).clicked() {
+                        if current.choices.is_empty() {
+                            wheel_to_open = Some(wheel.name.clone());
+                        } else {
+                            self.pending = Some(Pending::Open(wheel.name.clone()));
+                            modal.open();
+                        }
+                    }
+                    let delete_response = ui.button("🗑");
+                    accessibility::set_name(
+                        &delete_response,
+                        t!("library-delete", name = wheel.name.as_str()),
+                    );
+                    if delete_response.clicked() {
+                        wheel_to_delete = Some(i);
+                    }
+                });
+            }
+        });
+
+        if let Some(i) = wheel_to_delete {
+            self.wheels.remove(i);
+        }
+
+        modal.show(|ui| {
+            let message = match &self.pending {
+                Some(Pending::Overwrite) => {
+                    t!("library-overwrite", name = current.name.as_str())
+                }
+                Some(Pending::Open(name)) => t!("library-open-confirm", name = name.as_str()),
+                None => String::new(),
+            };
+            modal.title(ui, t!("library-confirm-title"));
+            modal.frame(ui, |ui| {
+                modal.body(ui, message);
+            });
+            modal.buttons(ui, |ui| {
+                if modal.button(ui, t!("cancel-button")).clicked() {
+                    self.pending = None;
+                }
+                if modal.button(ui, t!("confirm-button")).clicked() {
+                    match self.pending.take() {
+                        Some(Pending::Overwrite) => self.save(current),
+                        Some(Pending::Open(name)) => wheel_to_open = Some(name),
+                        None => {}
+                    }
+                }
+            });
+        });
+        wheel_to_open
+    }
+}
+
+impl ResultHistory {
+    pub fn record(&mut self, text: String) {
+        self.results.insert(
+            0,
+            Outcome {
+                text,
+                time: fairness::now(),
+            },
+        );
+        self.results.truncate(constants::MAX_RESULTS);
+    }
+
+    pub fn draw(&mut self, ui: &mut egui::Ui) {
+        if self.results.is_empty() {
+            ui.weak(t!("results-empty"));
+            return;
+        }
+
+        let now = fairness::now();
+        egui::ScrollArea::vertical()
+            .id_salt("results")
+            .max_height(ui.spacing().interact_size.y * 6.0)
+            .show(ui, |ui| {
+                for outcome in self.results.iter() {
+                    ui.horizontal(|ui| {
+                        ui.weak(elapsed_text(now.saturating_sub(outcome.time)));
+                        ui.label(&outcome.text);
+                    });
+                }
+            });
+        if ui.button(t!("results-clear")).clicked() {
+            self.results.clear();
+        }
+    }
+}
+
+fn elapsed_text(seconds: u64) -> String {
+    match seconds {
+        0..60 => t!("elapsed-now"),
+        60..3600 => t!("elapsed-minutes", count = seconds / 60),
+        3600..86400 => t!("elapsed-hours", count = seconds / 3600),
+        _ => t!("elapsed-days", count = seconds / 86400),
+    }
+}
@@ -0,0 +1,377 @@
+use eframe::egui::{self, Color32, Pos2, Rect, Stroke};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+use super::{constants, i18n::t};
+
+// How the wheel moves once launched, saved with the wheel
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpinPhysics {
+    // Range of the spin duration, in seconds
+    pub min_duration: f32,
+    pub max_duration: f32,
+    // Full turns done before the wheel starts looking for its landing angle
+    pub min_rotations: u32,
+    pub direction: SpinDirection,
+    pub curve: DecelerationCurve,
+    // Go a bit past the landing angle and come back
+    pub bounce: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SpinDirection {
+    Clockwise,
+    CounterClockwise,
+    Random,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DecelerationCurve {
+    Exponential,
+    Linear,
+    EaseOutCubic,
+}
+
+// A spin in progress: the wheel travels `distance` radians in `duration` seconds
+pub struct Spin {
+    start_rotation: f32,
+    distance: f32,
+    duration: f32,
+    elapsed: f32,
+    curve: DecelerationCurve,
+    bounce: bool,
+}
+
+impl Default for SpinPhysics {
+    fn default() -> Self {
+        Self {
+            min_duration: 6.0,
+            max_duration: 10.0,
+            min_rotations: 5,
+            direction: SpinDirection::Clockwise,
+            curve: DecelerationCurve::Exponential,
+            bounce: false,
+        }
+    }
+}
+
+impl SpinPhysics {
+    pub fn random_duration(&self, rng: &mut impl Rng) -> f32 {
+        let (min, max) = self.duration_range();
+        if max > min {
+            rng.random_range(min..=max)
+        } else {
+            min
+        }
+    }
+
+    // Duration of a launch with the given strength, from 0 to 1
+    pub fn duration_for_strength(&self, strength: f32) -> f32 {
+        let (min, max) = self.duration_range();
+        min + (max - min) * strength.clamp(0.0, 1.0)
+    }
+
+    // 1 for clockwise, -1 for counter-clockwise
+    pub fn random_direction(&self, rng: &mut impl Rng) -> f32 {
+        match self.direction {
+            SpinDirection::Clockwise => 1.0,
+            SpinDirection::CounterClockwise => -1.0,
+            SpinDirection::Random => {
+                if rng.random_bool(0.5) {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+        }
+    }
+
+    // Whole turns of a spin, longer spins go around more times
+    pub fn full_turns(&self, duration: f32) -> f32 {
+        self.min_rotations as f32 + (duration * constants::TURNS_PER_SECOND).round()
+    }
+
+    // Distance and duration of a spin starting at `speed` radians per second.
+    // The wheel does the whole turns it would do in `duration` at that speed,
+    // then the duration is solved so that the curve starts at `speed`.
+    pub fn flick(&self, speed: f32, duration: f32, landing_offset: f32) -> (f32, f32) {
+        let speed = speed.max(f32::EPSILON);
+        let turns = (speed * duration / self.curve.initial_speed() / (2.0 * PI)).floor();
+        let distance = turns * 2.0 * PI + landing_offset;
+        // The bounce overshoots the distance in a shorter time
+        let travelled = if self.bounce {
+            (distance + constants::BOUNCE_ANGLE) / (1.0 - constants::BOUNCE_TIME)
+        } else {
+            distance
+        };
+        let duration = travelled * self.curve.initial_speed() / speed;
+        (
+            distance,
+            duration.clamp(constants::MIN_SPIN_DURATION, constants::MAX_SPIN_DURATION),
+        )
+    }
+
+    fn duration_range(&self) -> (f32, f32) {
+        let min = self
+            .min_duration
+            .clamp(constants::MIN_SPIN_DURATION, constants::MAX_SPIN_DURATION);
+        (
+            min,
+            self.max_duration.clamp(min, constants::MAX_SPIN_DURATION),
+        )
+    }
+
+    pub fn draw(&mut self, ui: &mut egui::Ui, enabled: bool) {
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("physics-duration"));
+                ui.add(
+                    egui::DragValue::new(&mut self.min_duration)
+                        .range(constants::MIN_SPIN_DURATION..=constants::MAX_SPIN_DURATION)
+                        .speed(0.1)
+                        .suffix(" s"),
+                );
+                ui.label(t!("physics-to"));
+                ui.add(
+                    egui::DragValue::new(&mut self.max_duration)
+                        .range(self.min_duration..=constants::MAX_SPIN_DURATION)
+                        .speed(0.1)
+                        .suffix(" s"),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("physics-min-rotations"));
+                ui.add(
+                    egui::DragValue::new(&mut self.min_rotations)
+                        .range(0..=constants::MAX_MIN_ROTATIONS),
+                );
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t!("physics-direction"));
+                for direction in [
+                    SpinDirection::Clockwise,
+                    SpinDirection::CounterClockwise,
+                    SpinDirection::Random,
+                ] {
+                    ui.selectable_value(&mut self.direction, direction, direction.label());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("physics-curve"));
+                egui::ComboBox::from_id_salt("deceleration_curve")
+                    .selected_text(self.curve.label())
+                    .show_ui(ui, |ui| {
+                        for curve in [
+                            DecelerationCurve::Exponential,
+                            DecelerationCurve::Linear,
+                            DecelerationCurve::EaseOutCubic,
+                        ] {
+                            ui.selectable_value(&mut self.curve, curve, curve.label());
+                        }
+                    });
+                ui.checkbox(&mut self.bounce, t!("physics-bounce"));
+            });
+        });
+
+        self.draw_preview(ui);
+    }
+
+    // Speed of the wheel over time as bars, with the travelled angle on top.
+    // Both follow the curve and the bounce of the settings.
+    fn draw_preview(&self, ui: &mut egui::Ui) {
+        let (min, max) = self.duration_range();
+        let spin = Spin::new(0.0, 1.0, 1.0, self.curve, self.bounce);
+        let bins = constants::PREVIEW_BINS;
+        // Average speed over each part of the spin, going back counts as stopped
+        let speeds: Vec<f32> = (0..bins)
+            .map(|i| {
+                let start = spin.progress(i as f32 / bins as f32, constants::PREVIEW_BOUNCE_SCALE);
+                let end = spin.progress(
+                    (i + 1) as f32 / bins as f32,
+                    constants::PREVIEW_BOUNCE_SCALE,
+                );
+                (end - start).max(0.0)
+            })
+            .collect();
+        let highest = speeds.iter().copied().fold(f32::EPSILON, f32::max);
+
+        let size = egui::vec2(ui.available_width(), constants::PREVIEW_HEIGHT);
+        let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+
+        let bin_width = rect.width() / bins as f32;
+        for (i, speed) in speeds.iter().enumerate() {
+            let height = rect.height() * speed / highest;
+            let left = rect.left() + i as f32 * bin_width;
+            painter.rect_filled(
+                Rect::from_min_max(
+                    Pos2::new(left + 1.0, rect.bottom() - height),
+                    Pos2::new(left + bin_width - 1.0, rect.bottom()),
+                ),
+                0.0,
+                visuals.selection.bg_fill,
+            );
+        }
+
+        let points: Vec<Pos2> = (0..=constants::PREVIEW_CURVE_POINTS)
+            .map(|i| {
+                let t = i as f32 / constants::PREVIEW_CURVE_POINTS as f32;
+                let progress = spin.progress(t, constants::PREVIEW_BOUNCE_SCALE);
+                Pos2::new(
+                    rect.left() + t * rect.width(),
+                    rect.bottom() - progress * rect.height() * 0.9,
+                )
+            })
+            .collect();
+        painter.add(egui::Shape::line(
+            points,
+            Stroke::new(2.0, Color32::from_rgb(238, 178, 17)),
+        ));
+
+        ui.weak(t!(
+            "physics-summary",
+            min = format!("{min:.1}"),
+            max = format!("{max:.1}"),
+            min_turns = self.full_turns(min),
+            max_turns = self.full_turns(max)
+        ));
+    }
+}
+
+impl SpinDirection {
+    fn label(&self) -> String {
+        match self {
+            SpinDirection::Clockwise => t!("physics-clockwise"),
+            SpinDirection::CounterClockwise => t!("physics-counter-clockwise"),
+            SpinDirection::Random => t!("physics-random"),
+        }
+    }
+}
+
+impl DecelerationCurve {
+    fn label(&self) -> String {
+        match self {
+            DecelerationCurve::Exponential => t!("physics-exponential"),
+            DecelerationCurve::Linear => t!("physics-linear"),
+            DecelerationCurve::EaseOutCubic => t!("physics-ease-out"),
+        }
+    }
+
+    // Part of the distance travelled after `t` of the duration, both from 0 to 1
+    fn progress(&self, t: f32) -> f32 {
+        match self {
+            DecelerationCurve::Exponential => {
+                let decay = constants::EXPONENTIAL_DECAY;
+                (1.0 - (-decay * t).exp()) / (1.0 - (-decay).exp())
+            }
+            // The speed goes down by the same amount every second
+            DecelerationCurve::Linear => 1.0 - (1.0 - t).powi(2),
+            DecelerationCurve::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+        }
+    }
+
+    // Slope of `progress` at the start, the starting speed of a spin of one
+    // radian in one second
+    fn initial_speed(&self) -> f32 {
+        match self {
+            DecelerationCurve::Exponential => {
+                let decay = constants::EXPONENTIAL_DECAY;
+                decay / (1.0 - (-decay).exp())
+            }
+            DecelerationCurve::Linear => 2.0,
+            DecelerationCurve::EaseOutCubic => 3.0,
+        }
+    }
+}
+
+impl Spin {
+    // `distance` is negative for counter-clockwise spins
+    pub fn new(
+        start_rotation: f32,
+        distance: f32,
+        duration: f32,
+        curve: DecelerationCurve,
+        bounce: bool,
+    ) -> Self {
+        Self {
+            start_rotation,
+            distance,
+            duration: duration.max(constants::MIN_SPIN_DURATION),
+            elapsed: 0.0,
+            curve,
+            bounce,
+        }
+    }
+
+    // Advance by `dt` seconds, returns the rotation of the wheel
+    pub fn advance(&mut self, dt: f32) -> f32 {
+        self.elapsed += dt;
+        let t = (self.elapsed / self.duration).min(1.0);
+        let bounce_angle = constants::BOUNCE_ANGLE / self.distance.abs().max(f32::EPSILON);
+        self.start_rotation + self.distance * self.progress(t, bounce_angle)
+    }
+
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    pub fn end_rotation(&self) -> f32 {
+        self.start_rotation + self.distance
+    }
+
+    // With a bounce, the wheel overshoots by `bounce` of the distance, then
+    // eases back during the last part of the spin
+    fn progress(&self, t: f32, bounce: f32) -> f32 {
+        if t >= 1.0 {
+            return 1.0;
+        }
+        if !self.bounce {
+            return self.curve.progress(t);
+        }
+
+        let main_part = 1.0 - constants::BOUNCE_TIME;
+        if t < main_part {
+            (1.0 + bounce) * self.curve.progress(t / main_part)
+        } else {
+            let back = (t - main_part) / constants::BOUNCE_TIME;
+            1.0 + bounce * (1.0 + (PI * back).cos()) / 2.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecelerationCurve, Spin, SpinPhysics};
+
+    #[test]
+    fn flick_speed_carries_into_the_spin() {
+        let dt = 0.0001;
+        for curve in [
+            DecelerationCurve::Exponential,
+            DecelerationCurve::Linear,
+            DecelerationCurve::EaseOutCubic,
+        ] {
+            for bounce in [false, true] {
+                let physics = SpinPhysics {
+                    curve,
+                    bounce,
+                    ..SpinPhysics::default()
+                };
+                for speed in [10.0, 25.0, 50.0] {
+                    let (distance, duration) = physics.flick(speed, 8.0, 1.0);
+                    let mut spin = Spin::new(0.0, distance, duration, curve, bounce);
+                    let start_speed = spin.advance(dt) / dt;
+                    assert!(
+                        (start_speed - speed).abs() < speed * 0.01,
+                        "{curve:?} {bounce} {speed}: {start_speed}"
+                    );
+                }
+            }
+        }
+    }
+}
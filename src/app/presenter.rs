@@ -0,0 +1,133 @@
+use eframe::egui::{self, Align2, FontId, ViewportBuilder, ViewportClass, ViewportId};
+
+use super::{constants, i18n::t, App};
+
+// State of the presenter window
+#[derive(Default)]
+pub struct Presenter {
+    pub open: bool,
+    fullscreen: bool,
+}
+
+impl App {
+    // Big wheel in its own window, drawn from the same state as the main one
+    pub(super) fn show_presenter(&mut self, ctx: &egui::Context) {
+        if !self.presenter.open {
+            return;
+        }
+
+        let viewport = ViewportBuilder::default()
+            .with_title(t!("presenter-title"))
+            .with_inner_size([800.0, 800.0]);
+        ctx.show_viewport_immediate(
+            ViewportId::from_hash_of("presenter"),
+            viewport,
+            |ctx, class| {
+                if class == ViewportClass::Embedded {
+                    // Only one window is available, show it inside the main one
+                    let mut open = true;
+                    egui::Window::new(t!("presenter-window"))
+                        .id(egui::Id::new("presenter"))
+                        .open(&mut open)
+                        .show(ctx, |ui| {
+                            ui.set_min_size(egui::vec2(400.0, 400.0));
+                            self.draw_presenter(ui);
+                        });
+                    self.presenter.open = open;
+                    return;
+                }
+
+                let background = self.appearance.theme().background;
+                egui::CentralPanel::default()
+                    .frame(egui::Frame::central_panel(&ctx.style()).fill(background))
+                    .show(ctx, |ui| self.draw_presenter(ui));
+
+                if ctx.input(|i| i.key_pressed(egui::Key::F11)) {
+                    self.presenter.fullscreen = !self.presenter.fullscreen;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(
+                        self.presenter.fullscreen,
+                    ));
+                }
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    self.presenter.open = false;
+                    self.presenter.fullscreen = false;
+                }
+            },
+        );
+    }
+
+    fn draw_presenter(&mut self, ui: &mut egui::Ui) {
+        let rect = ui.max_rect();
+        let overlay_height = constants::TITLE_SIZE * 2.0;
+        let wheel_rect = rect.shrink2(egui::vec2(0.0, overlay_height));
+
+        // The wheel is laid out for the main window, borrow it for this one
+        let layout = (self.wheel.center, self.wheel.radius);
+        self.wheel.center = wheel_rect.center();
+        self.wheel.radius =
+            wheel_rect.width().min(wheel_rect.height()) / 2.0 * constants::PRESENTER_WHEEL_SIZE;
+
+        let painter = ui.painter_at(rect);
+        let highlight = self.celebration.highlight(ui.input(|i| i.time));
+        let theme = self.appearance.theme();
+        self.wheel
+            .draw(&painter, &self.wheel_choices, highlight.as_ref(), theme);
+        if self.wheel_choices.has_active() {
+            self.wheel
+                .draw_pointers(&painter, &self.wheel_choices.spin_settings, theme);
+        }
+
+        // Clicking the wheel spins it
+        let response = ui.interact(
+            egui::Rect::from_center_size(
+                self.wheel.center,
+                egui::Vec2::splat(self.wheel.radius * 2.0),
+            ),
+            egui::Id::new("presenter_wheel"),
+            egui::Sense::click(),
+        );
+        let can_spin = self.can_spin();
+        if can_spin
+            && response
+                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                .clicked()
+        {
+            self.wheel.start_spin(&self.wheel_choices);
+        }
+
+        (self.wheel.center, self.wheel.radius) = layout;
+
+        // Winner overlay
+        let visuals = ui.visuals();
+        let winners = self.wheel.winners();
+        if !self.wheel.spinning && !winners.is_empty() {
+            let text = winners
+                .iter()
+                .map(|choice| choice.label.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ");
+            painter.text(
+                rect.center_top() + egui::vec2(0.0, overlay_height / 2.0),
+                Align2::CENTER_CENTER,
+                text,
+                FontId::proportional(constants::TITLE_SIZE * 1.5),
+                visuals.strong_text_color(),
+            );
+        } else if !self.wheel_choices.name.is_empty() {
+            painter.text(
+                rect.center_top() + egui::vec2(0.0, overlay_height / 2.0),
+                Align2::CENTER_CENTER,
+                &self.wheel_choices.name,
+                FontId::proportional(constants::TITLE_SIZE),
+                visuals.text_color(),
+            );
+        }
+        painter.text(
+            rect.center_bottom() - egui::vec2(0.0, overlay_height / 2.0),
+            Align2::CENTER_CENTER,
+            t!("presenter-hint"),
+            FontId::proportional(constants::TITLE_SIZE / 2.0),
+            visuals.weak_text_color(),
+        );
+    }
+}
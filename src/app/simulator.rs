@@ -0,0 +1,648 @@
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    sync::Arc,
+    thread::JoinHandle,
+};
+
+use egui::{Context, ProgressBar};
+use rand::Rng;
+
+use super::{constants, i18n::t, wheel::Wheel, WheelChoices};
+
+pub struct FairnessReport {
+    pub spins: u64,
+    pub rows: Vec<FairnessRow>,
+    pub chi_square: f64,
+    pub degrees_of_freedom: usize,
+    pub p_value: f64,
+}
+
+pub struct FairnessRow {
+    pub label: String,
+    pub expected: f64,
+    pub wins: u64,
+}
+
+// "Verify fairness" window
+pub struct FairnessDialog {
+    pub open: bool,
+    spins: u64,
+    simulation: Option<Simulation>,
+    report: Option<FairnessReport>,
+}
+
+// Simulation running on its own thread
+#[cfg(not(target_arch = "wasm32"))]
+struct Simulation {
+    spins: u64,
+    progress: Arc<AtomicU64>,
+    cancel: Arc<AtomicBool>,
+    handle: JoinHandle<Option<FairnessReport>>,
+}
+
+// Browsers have no threads, the simulation runs a few spins each frame
+#[cfg(target_arch = "wasm32")]
+struct Simulation {
+    spins: u64,
+    wheel_choices: WheelChoices,
+    // Boxed, the simulation is moved in and out of `poll` every frame
+    tally: Option<Box<Tally>>,
+    cancelled: bool,
+}
+
+// Wins of each segment so far, a simulation can be run in several parts
+struct Tally {
+    wheel: Wheel,
+    ids: Vec<u32>,
+    wins: Vec<u64>,
+    spins: u64,
+}
+
+// Spin the wheel `spins` times with the same physics as the app, without
+// rendering. Fair rotation is not updated so every spin has the same odds.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn simulate(
+    rng: &mut impl Rng,
+    wheel_choices: &WheelChoices,
+    spins: u64,
+    progress: &AtomicU64,
+    cancel: &AtomicBool,
+) -> Option<FairnessReport> {
+    let mut tally = Tally::new(wheel_choices)?;
+    while tally.spins < spins {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        progress.store(tally.spins, Ordering::Relaxed);
+        tally.spin(
+            rng,
+            wheel_choices,
+            constants::SIMULATION_PROGRESS_STEP.min(spins - tally.spins),
+        );
+    }
+    progress.store(spins, Ordering::Relaxed);
+    Some(tally.report(wheel_choices))
+}
+
+impl Tally {
+    fn new(wheel_choices: &WheelChoices) -> Option<Self> {
+        let segments = Wheel::get_segments(wheel_choices);
+        if segments.is_empty() {
+            return None;
+        }
+
+        let mut wheel = Wheel::new();
+        wheel.reset_rotation(&wheel_choices.choices);
+        Some(Self {
+            wheel,
+            ids: segments.iter().map(|segment| segment.choice.id).collect(),
+            wins: vec![0; segments.len()],
+            spins: 0,
+        })
+    }
+
+    fn spin(&mut self, rng: &mut impl Rng, wheel_choices: &WheelChoices, count: u64) {
+        for _ in 0..count {
+            self.wheel.start_spin_with(rng, wheel_choices);
+            // Only the landing position matters, skip the animation
+            while !self.wheel.step(f32::INFINITY) {}
+            // With several pointers, only the first one is counted
+            if let Some(winner) = self.wheel.finish_spin(wheel_choices).first() {
+                if let Some(index) = self.ids.iter().position(|id| *id == winner.id) {
+                    self.wins[index] += 1;
+                }
+            }
+        }
+        self.spins += count;
+    }
+
+    fn report(self, wheel_choices: &WheelChoices) -> FairnessReport {
+        let spins = self.spins;
+        let rows: Vec<FairnessRow> = Wheel::get_segments(wheel_choices)
+            .iter()
+            .zip(self.wins)
+            .map(|(segment, wins)| FairnessRow {
+                label: segment.choice.label.clone(),
+                expected: segment.probability,
+                wins,
+            })
+            .collect();
+
+        let chi_square: f64 = rows
+            .iter()
+            .map(|row| {
+                let expected = row.expected * spins as f64;
+                (row.wins as f64 - expected).powi(2) / expected
+            })
+            .sum();
+        let degrees_of_freedom = rows.len() - 1;
+        let p_value = if degrees_of_freedom == 0 {
+            1.0
+        } else {
+            gamma_q(degrees_of_freedom as f64 / 2.0, chi_square / 2.0)
+        };
+
+        FairnessReport {
+            spins,
+            rows,
+            chi_square,
+            degrees_of_freedom,
+            p_value,
+        }
+    }
+}
+
+// `cargo_spin simulate` entry point, returns the printed report
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_simulation_cli(args: &[String]) -> Result<String, String> {
+    let usage = t!("cli-usage");
+    let mut wheel_choices = WheelChoices::new();
+    let mut spins = constants::CLI_SIMULATION_SPINS;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--spins" => {
+                spins = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|spins| *spins > 0)
+                    .ok_or_else(|| format!("{}\n{usage}", t!("cli-invalid-spins")))?;
+            }
+            "--winner-first" => wheel_choices.spin_settings.winner_first = true,
+            "-h" | "--help" => return Ok(usage),
+            label => {
+                let (label, weight) = match label.rsplit_once(':') {
+                    Some((name, weight)) => match weight.parse::<f64>() {
+                        Ok(weight) => (name, weight),
+                        Err(_) => (label, 1.0),
+                    },
+                    None => (label, 1.0),
+                };
+                if !(weight.is_finite() && weight >= constants::MIN_SEGMENT_WEIGHT) {
+                    return Err(format!(
+                        "{}\n{usage}",
+                        t!("cli-invalid-weight", label = label)
+                    ));
+                }
+
+                let mut choice = wheel_choices.create_choice(label.to_owned());
+                choice.weight = weight;
+                wheel_choices.choices.push(choice);
+            }
+        }
+    }
+
+    if wheel_choices.choices.is_empty() {
+        return Err(usage);
+    }
+
+    simulate(
+        &mut rand::rng(),
+        &wheel_choices,
+        spins,
+        &AtomicU64::new(0),
+        &AtomicBool::new(false),
+    )
+    .map(|report| report.to_string())
+    .ok_or(usage)
+}
+
+impl FairnessDialog {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            spins: constants::GUI_SIMULATION_SPINS,
+            simulation: None,
+            report: None,
+        }
+    }
+
+    pub fn draw(&mut self, ctx: &Context, wheel_choices: &WheelChoices) {
+        let mut open = self.open;
+        egui::Window::new(t!("verify-fairness"))
+            .id(egui::Id::new("fairness_dialog"))
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(t!("simulation-description"));
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("simulation-spins"));
+                    ui.add_enabled(
+                        self.simulation.is_none(),
+                        egui::DragValue::new(&mut self.spins)
+                            .range(1..=constants::MAX_SIMULATION_SPINS)
+                            .speed(1000),
+                    );
+
+                    match &self.simulation {
+                        None => {
+                            if ui
+                                .add_enabled(
+                                    wheel_choices.has_active(),
+                                    egui::Button::new(t!("simulation-run")),
+                                )
+                                .clicked()
+                            {
+                                self.start(wheel_choices);
+                            }
+                        }
+                        Some(_) => {
+                            if ui.button(t!("cancel-button")).clicked() {
+                                self.cancel();
+                            }
+                        }
+                    }
+                });
+
+                if let Some(simulation) = &self.simulation {
+                    let done = simulation.done();
+                    ui.add(
+                        ProgressBar::new(done as f32 / simulation.spins as f32).show_percentage(),
+                    );
+                }
+
+                if let Some(report) = &self.report {
+                    ui.separator();
+                    report.draw(ui);
+                }
+            });
+        self.open = open;
+
+        if !self.open {
+            self.cancel();
+        }
+        self.poll(ctx);
+    }
+
+    fn start(&mut self, wheel_choices: &WheelChoices) {
+        // The simulation runs on a copy so the wheel can still be edited
+        self.report = None;
+        self.simulation = Some(Simulation::start(wheel_choices.snapshot(), self.spins));
+    }
+
+    fn cancel(&mut self) {
+        if let Some(simulation) = &mut self.simulation {
+            simulation.cancel();
+        }
+    }
+
+    fn poll(&mut self, ctx: &Context) {
+        let Some(simulation) = self.simulation.take() else {
+            return;
+        };
+
+        match simulation.poll() {
+            Ok(report) => self.report = report,
+            Err(simulation) => {
+                self.simulation = Some(simulation);
+                ctx.request_repaint();
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Simulation {
+    fn start(wheel_choices: WheelChoices, spins: u64) -> Self {
+        let progress = Arc::new(AtomicU64::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let progress = progress.clone();
+            let cancel = cancel.clone();
+            std::thread::spawn(move || {
+                simulate(&mut rand::rng(), &wheel_choices, spins, &progress, &cancel)
+            })
+        };
+
+        Self {
+            spins,
+            progress,
+            cancel,
+            handle,
+        }
+    }
+
+    fn done(&self) -> u64 {
+        self.progress.load(Ordering::Relaxed)
+    }
+
+    fn cancel(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    // The report once the simulation is over, the simulation itself otherwise
+    fn poll(self) -> Result<Option<FairnessReport>, Self> {
+        if self.handle.is_finished() {
+            Ok(self.handle.join().ok().flatten())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Simulation {
+    fn start(wheel_choices: WheelChoices, spins: u64) -> Self {
+        Self {
+            spins,
+            tally: Tally::new(&wheel_choices).map(Box::new),
+            wheel_choices,
+            cancelled: false,
+        }
+    }
+
+    fn done(&self) -> u64 {
+        self.tally.as_ref().map_or(0, |tally| tally.spins)
+    }
+
+    fn cancel(&mut self) {
+        self.cancelled = true;
+    }
+
+    // The report once the simulation is over, the simulation itself otherwise
+    fn poll(mut self) -> Result<Option<FairnessReport>, Self> {
+        let Some(tally) = self.tally.as_mut().filter(|_| !self.cancelled) else {
+            return Ok(None);
+        };
+        if tally.spins < self.spins {
+            tally.spin(
+                &mut rand::rng(),
+                &self.wheel_choices,
+                constants::SIMULATION_FRAME_SPINS.min(self.spins - tally.spins),
+            );
+            return Err(self);
+        }
+        Ok(self.tally.map(|tally| tally.report(&self.wheel_choices)))
+    }
+}
+
+impl FairnessReport {
+    fn draw(&self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical()
+            .max_height(ui.spacing().interact_size.y * 12.0)
+            .show(ui, |ui| {
+                egui::Grid::new("fairness_report")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(t!("report-choice"));
+                        ui.strong(t!("report-expected"));
+                        ui.strong(t!("report-observed"));
+                        ui.strong(t!("report-wins"));
+                        ui.end_row();
+
+                        for row in self.rows.iter() {
+                            ui.add(egui::Label::new(&row.label).truncate());
+                            ui.label(format!("{:.3} %", row.expected * 100.0));
+                            ui.label(format!("{:.3} %", row.observed(self.spins) * 100.0));
+                            ui.label(row.wins.to_string());
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        ui.add_space(constants::SPACER_AMOUNT / 2.0);
+        ui.label(t!(
+            "report-statistic",
+            chi_square = format!("{:.3}", self.chi_square),
+            degrees = self.degrees_of_freedom,
+            p_value = format!("{:.4}", self.p_value)
+        ));
+        ui.label(self.verdict());
+    }
+
+    fn verdict(&self) -> String {
+        if self.p_value >= constants::FAIRNESS_SIGNIFICANCE {
+            t!("report-fair")
+        } else {
+            t!("report-unfair")
+        }
+    }
+}
+
+impl FairnessRow {
+    fn observed(&self, spins: u64) -> f64 {
+        self.wins as f64 / spins as f64
+    }
+}
+
+impl fmt::Display for FairnessReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = t!("report-choice");
+        let width = self
+            .rows
+            .iter()
+            .map(|row| row.label.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(header.chars().count());
+
+        writeln!(
+            f,
+            "{:<width$}  {:>10}  {:>10}  {:>12}",
+            header,
+            t!("report-expected"),
+            t!("report-observed"),
+            t!("report-wins")
+        )?;
+        for row in self.rows.iter() {
+            writeln!(
+                f,
+                "{:<width$}  {:>9.3}%  {:>9.3}%  {:>12}",
+                row.label,
+                row.expected * 100.0,
+                row.observed(self.spins) * 100.0,
+                row.wins
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{}",
+            t!(
+                "report-summary",
+                spins = self.spins,
+                chi_square = format!("{:.3}", self.chi_square),
+                degrees = self.degrees_of_freedom,
+                p_value = format!("{:.4}", self.p_value)
+            )
+        )?;
+        write!(f, "{}", self.verdict())
+    }
+}
+
+// Upper regularized incomplete gamma function Q(a, x), which gives the p-value
+// of a chi-square statistic (Numerical Recipes, 6.2)
+fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < a + 1.0 {
+        1.0 - gamma_p_series(a, x)
+    } else {
+        gamma_q_continued_fraction(a, x)
+    }
+}
+
+fn gamma_p_series(a: f64, x: f64) -> f64 {
+    let mut sum = 1.0 / a;
+    let mut term = sum;
+    let mut n = a;
+    for _ in 0..constants::GAMMA_MAX_ITERATIONS {
+        n += 1.0;
+        term *= x / n;
+        sum += term;
+        if term.abs() < sum.abs() * f64::EPSILON {
+            break;
+        }
+    }
+    sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+fn gamma_q_continued_fraction(a: f64, x: f64) -> f64 {
+    let tiny = f64::MIN_POSITIVE / f64::EPSILON;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..=constants::GAMMA_MAX_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = b + an / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < f64::EPSILON {
+            break;
+        }
+    }
+    (-x + a * x.ln() - ln_gamma(a)).exp() * h
+}
+
+// Lanczos approximation
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000_000_000_190_015;
+    let mut y = x;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::TAU;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{gamma_q, ln_gamma, Tally};
+    use crate::app::{constants, wheel::Wheel, WheelChoices};
+
+    // Wheel with a choice per weight
+    fn weighted_wheel(weights: &[f64]) -> WheelChoices {
+        let mut wheel_choices = WheelChoices::new();
+        let mut wheel = Wheel::new();
+        for (index, weight) in weights.iter().enumerate() {
+            wheel_choices.add_segment(index.to_string(), &mut wheel);
+            let id = wheel_choices.choices[index].id;
+            wheel_choices.set_weight(id, *weight);
+        }
+        wheel_choices
+    }
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!((value - expected).abs() < 1e-3, "{value} != {expected}");
+    }
+
+    #[test]
+    fn chi_square_p_values() {
+        // Critical values at 5 % from the chi-square tables
+        assert_close(gamma_q(1.0 / 2.0, 3.841 / 2.0), 0.05);
+        assert_close(gamma_q(4.0 / 2.0, 9.488 / 2.0), 0.05);
+        assert_close(gamma_q(10.0 / 2.0, 23.209 / 2.0), 0.01);
+        assert_eq!(gamma_q(3.0, 0.0), 1.0);
+        assert!(gamma_q(1.0, 1000.0) < 1e-100);
+    }
+
+    #[test]
+    fn ln_gamma_of_known_values() {
+        assert_close(ln_gamma(5.0), 24f64.ln());
+        assert_close(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln());
+        assert_close(ln_gamma(1.0), 0.0);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn fair_wheel_passes() {
+        use std::sync::atomic::{AtomicBool, AtomicU64};
+
+        let wheel_choices = weighted_wheel(&[1.0, 2.0]);
+        let report = super::simulate(
+            &mut StdRng::seed_from_u64(7),
+            &wheel_choices,
+            30_000,
+            &AtomicU64::new(0),
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+        assert_eq!(report.spins, 30_000);
+        assert_eq!(report.rows[0].wins + report.rows[1].wins, 30_000);
+        assert!(
+            report.p_value >= constants::FAIRNESS_SIGNIFICANCE,
+            "p = {}",
+            report.p_value
+        );
+    }
+
+    #[test]
+    fn skewed_tally_fails() {
+        let wheel_choices = weighted_wheel(&[1.0, 2.0]);
+        let mut tally = Tally::new(&wheel_choices).unwrap();
+        // Even wins on a 1:2 wheel
+        tally.wins = vec![1500, 1500];
+        tally.spins = 3000;
+        let report = tally.report(&wheel_choices);
+        assert_eq!(report.degrees_of_freedom, 1);
+        assert!(report.p_value < constants::FAIRNESS_SIGNIFICANCE);
+    }
+
+    #[test]
+    fn rotation_stays_within_a_turn() {
+        let mut wheel_choices = WheelChoices::new();
+        let mut wheel = Wheel::new();
+        for label in ["A", "B", "C"] {
+            wheel_choices.add_segment(label.to_owned(), &mut wheel);
+        }
+
+        let mut tally = Tally::new(&wheel_choices).unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..1000 {
+            tally.spin(&mut rng, &wheel_choices, 100);
+            let rotation = tally.wheel.rotation();
+            assert!((0.0..TAU).contains(&rotation), "rotation {rotation}");
+        }
+        assert_eq!(tally.spins, 100_000);
+    }
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+
+use super::{constants, i18n::t, Choice};
+
+// Tags a choice needs to be spun, saved with the wheel
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TagFilter {
+    pub tags: Vec<String>,
+    pub mode: TagMatch,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum TagMatch {
+    // At least one of the tags
+    #[default]
+    Any,
+    // Every tag
+    All,
+}
+
+impl TagMatch {
+    fn label(&self) -> String {
+        match self {
+            TagMatch::Any => t!("tags-match-any"),
+            TagMatch::All => t!("tags-match-all"),
+        }
+    }
+}
+
+impl TagFilter {
+    pub fn is_active(&self) -> bool {
+        !self.tags.is_empty()
+    }
+
+    // Every choice matches an empty filter
+    pub fn matches(&self, choice: &Choice) -> bool {
+        let has_tag = |tag: &String| choice.tags.iter().any(|own| same_tag(own, tag));
+        match self.mode {
+            _ if self.tags.is_empty() => true,
+            TagMatch::Any => self.tags.iter().any(has_tag),
+            TagMatch::All => self.tags.iter().all(has_tag),
+        }
+    }
+
+    // Bar picking the tags of the next spin, hidden while no choice has tags
+    pub fn draw(&mut self, ui: &mut egui::Ui, choices: &[Choice], enabled: bool) {
+        let available = all_tags(choices);
+        // Tags removed from every choice can't be picked anymore
+        if enabled {
+            self.tags
+                .retain(|tag| available.iter().any(|other| same_tag(tag, other)));
+        }
+        if available.is_empty() {
+            return;
+        }
+
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(t!("tags-filter"));
+                for tag in &available {
+                    let position = self.tags.iter().position(|own| same_tag(own, tag));
+                    if ui
+                        .selectable_label(position.is_some(), format!("🏷 {tag}"))
+                        .clicked()
+                    {
+                        match position {
+                            Some(index) => {
+                                self.tags.remove(index);
+                            }
+                            None => self.tags.push(tag.clone()),
+                        }
+                    }
+                }
+
+                egui::ComboBox::from_id_salt("tag_match")
+                    .selected_text(self.mode.label())
+                    .show_ui(ui, |ui| {
+                        for mode in [TagMatch::Any, TagMatch::All] {
+                            ui.selectable_value(&mut self.mode, mode, mode.label());
+                        }
+                    });
+                if ui
+                    .add_enabled(self.is_active(), egui::Button::new(t!("tags-clear")))
+                    .clicked()
+                {
+                    self.tags.clear();
+                }
+            });
+        });
+    }
+}
+
+// Tags of a choice as removable chips, followed by a box to add one
+pub fn draw_editor(ui: &mut egui::Ui, tags: &mut Vec<String>, input: &mut String) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label(t!("tags-label"));
+        let mut tag_to_remove: Option<usize> = None;
+        for (index, tag) in tags.iter().enumerate() {
+            if ui
+                .button(format!("🏷 {tag} 🗙"))
+                .on_hover_text(t!("tags-remove"))
+                .clicked()
+            {
+                tag_to_remove = Some(index);
+            }
+        }
+        if let Some(index) = tag_to_remove {
+            tags.remove(index);
+        }
+
+        let response = ui.add(
+            egui::TextEdit::singleline(input)
+                .hint_text(t!("tags-hint"))
+                .char_limit(constants::MAX_TAG_LENGTH)
+                .desired_width(ui.spacing().interact_size.x * 2.0),
+        );
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        let tag = input.trim().to_owned();
+        let can_add = !tag.is_empty()
+            && tags.len() < constants::MAX_TAGS
+            && !tags.iter().any(|own| same_tag(own, &tag));
+        let clicked = ui
+            .add_enabled(can_add, egui::Button::new(t!("tags-add")))
+            .clicked();
+        if can_add && (clicked || submitted) {
+            tags.push(tag);
+            input.clear();
+            response.request_focus();
+        }
+    });
+}
+
+// Tags of all the choices, in the order they first appear
+fn all_tags(choices: &[Choice]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in choices.iter().flat_map(|choice| &choice.tags) {
+        if !tags.iter().any(|other| same_tag(other, tag)) {
+            tags.push(tag.clone());
+        }
+    }
+    tags
+}
+
+// "Vegetarian" and "vegetarian" are the same tag
+fn same_tag(a: &str, b: &str) -> bool {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .eq(b.chars().flat_map(char::to_lowercase))
+}
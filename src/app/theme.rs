@@ -0,0 +1,433 @@
+use eframe::egui::{self, Color32, Context, FontFamily, Stroke, Visuals};
+use serde::{Deserialize, Serialize};
+
+use super::{constants, i18n::t};
+
+pub const THEME_KEY: &str = "theme";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ThemeChoice {
+    System,
+    Light,
+    Dark,
+    HighContrast,
+    Custom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LabelFont {
+    Proportional,
+    Monospace,
+}
+
+// Colors of the window and the wheel, the custom one can be shared as a file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    // Given by the user, empty for the built-in themes whose names come from
+    // the translations
+    pub name: String,
+    // Light or dark widgets
+    pub dark: bool,
+    pub background: Color32,
+    pub palette: Vec<Color32>,
+    pub pointer: Color32,
+    pub label_color: Color32,
+    pub label_font: LabelFont,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSettings {
+    pub choice: ThemeChoice,
+    pub custom: Theme,
+    // Path used to export and import the custom theme
+    pub file: String,
+}
+
+// Theme in use, applied to egui at the start of each frame
+pub struct Appearance {
+    pub settings: ThemeSettings,
+    active: Theme,
+    pub editor_open: bool,
+    error: Option<String>,
+}
+
+impl ThemeChoice {
+    const ALL: [ThemeChoice; 5] = [
+        ThemeChoice::System,
+        ThemeChoice::Light,
+        ThemeChoice::Dark,
+        ThemeChoice::HighContrast,
+        ThemeChoice::Custom,
+    ];
+
+    fn label(&self) -> String {
+        match self {
+            ThemeChoice::System => t!("theme-system"),
+            ThemeChoice::Light => t!("theme-light"),
+            ThemeChoice::Dark => t!("theme-dark"),
+            ThemeChoice::HighContrast => t!("theme-high-contrast"),
+            ThemeChoice::Custom => t!("theme-custom"),
+        }
+    }
+}
+
+impl LabelFont {
+    pub fn family(&self) -> FontFamily {
+        match self {
+            LabelFont::Proportional => FontFamily::Proportional,
+            LabelFont::Monospace => FontFamily::Monospace,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            LabelFont::Proportional => t!("theme-font-proportional"),
+            LabelFont::Monospace => t!("theme-font-monospace"),
+        }
+    }
+}
+
+impl Theme {
+    fn light() -> Self {
+        Self {
+            name: String::new(),
+            dark: false,
+            background: Visuals::light().panel_fill,
+            palette: Self::default_palette(),
+            pointer: Color32::from_rgb(90, 90, 90),
+            label_color: Color32::WHITE,
+            label_font: LabelFont::Proportional,
+        }
+    }
+
+    fn dark() -> Self {
+        Self {
+            name: String::new(),
+            dark: true,
+            background: Visuals::dark().panel_fill,
+            palette: Self::default_palette(),
+            pointer: Color32::from_rgb(200, 200, 200),
+            label_color: Color32::WHITE,
+            label_font: LabelFont::Proportional,
+        }
+    }
+
+    // Dark segments with white labels on black, the pointer stands out in yellow
+    fn high_contrast() -> Self {
+        Self {
+            name: String::new(),
+            dark: true,
+            background: Color32::BLACK,
+            palette: vec![
+                Color32::from_rgb(0, 0, 170),
+                Color32::from_rgb(160, 0, 0),
+                Color32::from_rgb(0, 100, 0),
+                Color32::from_rgb(90, 0, 120),
+            ],
+            pointer: Color32::from_rgb(255, 215, 0),
+            label_color: Color32::WHITE,
+            label_font: LabelFont::Proportional,
+        }
+    }
+
+    fn default_palette() -> Vec<Color32> {
+        vec![
+            Color32::from_rgb(51, 105, 232),
+            Color32::from_rgb(213, 15, 37),
+            Color32::from_rgb(238, 178, 17),
+            Color32::from_rgb(0, 153, 37),
+        ]
+    }
+
+    fn visuals(&self) -> Visuals {
+        let mut visuals = if self.dark {
+            Visuals::dark()
+        } else {
+            Visuals::light()
+        };
+        visuals.panel_fill = self.background;
+        visuals
+    }
+
+    // A shared file can't be trusted to be usable as is
+    #[cfg(not(target_arch = "wasm32"))]
+    fn sanitized(mut self) -> Result<Self, String> {
+        if self.palette.is_empty() {
+            return Err(t!("theme-empty-palette"));
+        }
+        self.palette.truncate(constants::MAX_PALETTE_COLORS);
+        Ok(self)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        Self {
+            choice: ThemeChoice::System,
+            custom: Theme::default(),
+            file: String::new(),
+        }
+    }
+}
+
+fn high_contrast_visuals() -> Visuals {
+    let mut visuals = Visuals::dark();
+    visuals.panel_fill = Color32::BLACK;
+    visuals.window_fill = Color32::BLACK;
+    visuals.extreme_bg_color = Color32::BLACK;
+    visuals.faint_bg_color = Color32::from_gray(35);
+    visuals.window_stroke = Stroke::new(2.0, Color32::WHITE);
+    visuals.hyperlink_color = Color32::from_rgb(120, 200, 255);
+    visuals.selection.bg_fill = Color32::from_rgb(255, 215, 0);
+    visuals.selection.stroke = Stroke::new(2.0, Color32::BLACK);
+
+    let widgets = &mut visuals.widgets;
+    widgets.noninteractive.fg_stroke = Stroke::new(1.0, Color32::WHITE);
+    widgets.noninteractive.bg_stroke = Stroke::new(1.0, Color32::WHITE);
+    for state in [
+        &mut widgets.inactive,
+        &mut widgets.hovered,
+        &mut widgets.active,
+        &mut widgets.open,
+    ] {
+        state.bg_fill = Color32::BLACK;
+        state.weak_bg_fill = Color32::BLACK;
+        state.bg_stroke = Stroke::new(1.0, Color32::WHITE);
+        state.fg_stroke = Stroke::new(1.5, Color32::WHITE);
+    }
+    widgets.hovered.bg_stroke = Stroke::new(2.0, Color32::from_rgb(255, 215, 0));
+    widgets.active.bg_stroke = Stroke::new(2.0, Color32::from_rgb(255, 215, 0));
+    visuals
+}
+
+impl Appearance {
+    pub fn new(settings: ThemeSettings) -> Self {
+        Self {
+            settings,
+            active: Theme::dark(),
+            editor_open: false,
+            error: None,
+        }
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.active
+    }
+
+    // Picks the theme for this frame, egui is only told about changes
+    pub fn apply(&mut self, ctx: &Context) {
+        let (theme, visuals) = match self.settings.choice {
+            ThemeChoice::System => {
+                let theme = match ctx.system_theme() {
+                    Some(egui::Theme::Light) => Theme::light(),
+                    _ => Theme::dark(),
+                };
+                let visuals = theme.visuals();
+                (theme, visuals)
+            }
+            ThemeChoice::Light => (Theme::light(), Theme::light().visuals()),
+            ThemeChoice::Dark => (Theme::dark(), Theme::dark().visuals()),
+            ThemeChoice::HighContrast => (Theme::high_contrast(), high_contrast_visuals()),
+            ThemeChoice::Custom => {
+                let theme = self.settings.custom.clone();
+                let visuals = theme.visuals();
+                (theme, visuals)
+            }
+        };
+
+        let egui_theme = if theme.dark {
+            egui::Theme::Dark
+        } else {
+            egui::Theme::Light
+        };
+        if ctx.theme() != egui_theme {
+            ctx.set_theme(egui_theme);
+        }
+        if ctx.style().visuals != visuals {
+            ctx.set_visuals_of(egui_theme, visuals);
+        }
+        self.active = theme;
+    }
+
+    pub fn draw_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            for choice in ThemeChoice::ALL {
+                ui.selectable_value(&mut self.settings.choice, choice, choice.label());
+            }
+        });
+        if ui.button(t!("theme-edit")).clicked() {
+            self.settings.choice = ThemeChoice::Custom;
+            self.editor_open = true;
+        }
+    }
+
+    pub fn draw_editor(&mut self, ctx: &Context) {
+        let mut open = self.editor_open;
+        egui::Window::new(t!("theme-editor"))
+            .id(egui::Id::new("theme_editor"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("theme-start-from"));
+                    for (text, theme) in [
+                        (t!("theme-light"), Theme::light()),
+                        (t!("theme-dark"), Theme::dark()),
+                        (t!("theme-high-contrast"), Theme::high_contrast()),
+                    ] {
+                        if ui.button(text).clicked() {
+                            self.settings.custom = Theme {
+                                name: self.settings.custom.name.clone(),
+                                ..theme
+                            };
+                        }
+                    }
+                });
+                ui.separator();
+
+                let theme = &mut self.settings.custom;
+                egui::Grid::new("theme_editor_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(t!("theme-name"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut theme.name)
+                                .hint_text(t!("theme-custom")),
+                        );
+                        ui.end_row();
+
+                        ui.label(t!("theme-widgets"));
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(&mut theme.dark, false, t!("theme-light"));
+                            ui.selectable_value(&mut theme.dark, true, t!("theme-dark"));
+                        });
+                        ui.end_row();
+
+                        ui.label(t!("theme-background"));
+                        ui.color_edit_button_srgba(&mut theme.background);
+                        ui.end_row();
+
+                        ui.label(t!("theme-segments"));
+                        ui.horizontal_wrapped(|ui| {
+                            let mut color_to_remove: Option<usize> = None;
+                            let can_remove = theme.palette.len() > 1;
+                            for (i, color) in theme.palette.iter_mut().enumerate() {
+                                ui.color_edit_button_srgba(color).context_menu(|ui| {
+                                    if ui
+                                        .add_enabled(
+                                            can_remove,
+                                            egui::Button::new(t!("theme-remove-color")),
+                                        )
+                                        .clicked()
+                                    {
+                                        color_to_remove = Some(i);
+                                        ui.close_menu();
+                                    }
+                                });
+                            }
+                            if let Some(i) = color_to_remove {
+                                theme.palette.remove(i);
+                            }
+                            if ui
+                                .add_enabled(
+                                    theme.palette.len() < constants::MAX_PALETTE_COLORS,
+                                    egui::Button::new("+"),
+                                )
+                                .on_hover_text(t!("theme-add-color"))
+                                .clicked()
+                            {
+                                let last = theme.palette.last().copied();
+                                theme.palette.push(last.unwrap_or(Color32::GRAY));
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label(t!("theme-pointer"));
+                        ui.color_edit_button_srgba(&mut theme.pointer);
+                        ui.end_row();
+
+                        ui.label(t!("theme-label-color"));
+                        ui.color_edit_button_srgba(&mut theme.label_color);
+                        ui.end_row();
+
+                        ui.label(t!("theme-label-font"));
+                        egui::ComboBox::from_id_salt("label_font")
+                            .selected_text(theme.label_font.label())
+                            .show_ui(ui, |ui| {
+                                for font in [LabelFont::Proportional, LabelFont::Monospace] {
+                                    ui.selectable_value(&mut theme.label_font, font, font.label());
+                                }
+                            });
+                        ui.end_row();
+                    });
+                ui.weak(t!("theme-remove-color-hint"));
+                ui.separator();
+
+                self.draw_sharing(ui);
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+        self.editor_open = open;
+    }
+
+    // Theme file to export the custom theme to or import it from
+    #[cfg(not(target_arch = "wasm32"))]
+    fn draw_sharing(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.settings.file)
+                    .hint_text(t!("theme-file-hint")),
+            );
+            let has_file = !self.settings.file.trim().is_empty();
+            if ui
+                .add_enabled(has_file, egui::Button::new(t!("theme-export")))
+                .clicked()
+            {
+                self.error = self.export().err();
+            }
+            if ui
+                .add_enabled(has_file, egui::Button::new(t!("theme-import")))
+                .clicked()
+            {
+                self.error = self.import().err();
+            }
+        });
+    }
+
+    // The browser doesn't give access to the files
+    #[cfg(target_arch = "wasm32")]
+    fn draw_sharing(&mut self, ui: &mut egui::Ui) {
+        ui.weak(t!("theme-file-unavailable-web"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export(&self) -> Result<(), String> {
+        let path = self.settings.file.trim();
+        let text = ron::ser::to_string_pretty(&self.settings.custom, Default::default())
+            .map_err(|error| t!("theme-write-error", path = path, error = error.to_string()))?;
+        std::fs::write(path, text)
+            .map_err(|error| t!("theme-write-error", path = path, error = error.to_string()))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn import(&mut self) -> Result<(), String> {
+        let path = self.settings.file.trim();
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| t!("theme-read-error", path = path, error = error.to_string()))?;
+        let theme: Theme = ron::from_str(&text)
+            .map_err(|error| t!("theme-read-error", path = path, error = error.to_string()))?;
+        self.settings.custom = theme.sanitized()?;
+        self.settings.choice = ThemeChoice::Custom;
+        Ok(())
+    }
+}
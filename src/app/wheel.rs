@@ -1,22 +1,79 @@
+use super::accessibility;
+use super::celebration::Highlight;
 use super::constants;
+use super::description;
+use super::i18n::t;
+use super::keymap::Action;
+use super::physics::{Spin, SpinPhysics};
+use super::theme::Theme;
 use super::{Choice, WheelChoices};
 use eframe::{
-    egui::{self, Color32, Context, FontId, Painter, Pos2, Stroke},
-    epaint::PathShape,
+    egui::{self, Color32, Context, FontId, Painter, Pos2, Rect, Stroke},
+    epaint::{Mesh, PathShape},
+};
+use egui::{
+    accesskit::{self, Role},
+    emath::Rot2,
+    epaint::TextShape,
+    load::SizedTexture,
+    Align2,
 };
-use egui::{epaint::TextShape, Align2};
 use egui_modal::Modal;
 use rand::Rng;
-use std::f32::consts::PI;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
 
 pub struct Wheel {
     pub radius: f32,
     pub center: Pos2,
     pub spinning: bool,
+    // Between 0 and 2π except during a spin, a growing f32 would lose the
+    // precision of the landing angle
     rotation: f32,
-    spin_velocity: f32,
-    winner: Option<Choice>,
+    // Full turns taken off `rotation`, so that the sounds see a continuous angle
+    turns: i64,
+    spin: Option<Spin>,
+    // One winner per pointer
+    winners: Vec<Choice>,
+    // Winner drawn before spinning
+    planned_winner: Option<Choice>,
+    // Time and pointer angle of the recent drag positions
+    drag_samples: Vec<(f64, f32)>,
+}
+
+// Spin settings, saved with the wheel
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpinSettings {
+    // Draw the winner from the weights, then animate the wheel to it
+    pub winner_first: bool,
+    // Angles of the pointers in degrees, clockwise from the right edge
+    pub pointers: Vec<f32>,
+    pub duplicates: DuplicatePolicy,
+    pub physics: SpinPhysics,
+}
+
+// What happens when several pointers land on the same choice
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DuplicatePolicy {
+    Allow,
+    NextChoice,
+}
+
+// What the user chose to do with the winners
+pub enum WinnerEvent {
+    Closed(Vec<Choice>),
+    OpenSubWheel(Choice),
 }
+
+pub struct Segment<'a> {
+    pub choice: &'a Choice,
+    pub probability: f64,
+    // Angles relative to the rotation of the wheel
+    start_angle: f32,
+    end_angle: f32,
+}
+
 struct Point {
     x: f32,
     y: f32,
@@ -28,9 +85,12 @@ impl Wheel {
             center: Pos2::new(0.0, 0.0),
             radius: 0.0,
             rotation: 0.0,
+            turns: 0,
             spinning: false,
-            spin_velocity: 0.0,
-            winner: None,
+            spin: None,
+            winners: Vec::new(),
+            planned_winner: None,
+            drag_samples: Vec::new(),
         }
     }
 
@@ -39,120 +99,384 @@ impl Wheel {
         self.radius = 0.0;
         self.rotation = 0.0;
         self.spinning = false;
-        self.spin_velocity = 0.0;
+        self.spin = None;
+        self.planned_winner = None;
     }
 
-    pub fn do_spin(&mut self, ctx: &Context, wheel_choices: &mut WheelChoices) {
+    pub fn do_spin(
+        &mut self,
+        ctx: &Context,
+        wheel_choices: &mut WheelChoices,
+        actions: &[Action],
+    ) -> Option<WinnerEvent> {
         let modal = Modal::new(ctx, "winner_modal");
+        let mut event: Option<WinnerEvent> = None;
         if !self.spinning {
+            if modal.is_open() && actions.contains(&Action::CloseDialog) {
+                modal.close();
+                event = Some(WinnerEvent::Closed(self.take_winners(wheel_choices)));
+            }
             modal.show(|ui| {
                 modal.frame(ui, |ui| {
                     egui::ScrollArea::vertical()
                         .max_height(ui.spacing().interact_size.y * 10.0)
                         .show(ui, |ui: &mut egui::Ui| {
-                            ui.add(egui::Label::new(
-                                egui::RichText::new(match &self.winner {
-                                    Some(choice) => choice.label.clone(),
-                                    None => String::from(""),
-                                })
-                                .font(FontId::proportional(constants::TITLE_SIZE)),
-                            ))
+                            let several_winners = self.winners.len() > 1;
+                            for (i, choice) in self.winners.iter().enumerate() {
+                                let label = if several_winners {
+                                    format!("{}. {}", i + 1, choice.label)
+                                } else {
+                                    choice.label.clone()
+                                };
+                                ui.horizontal(|ui| {
+                                    if let Some(icon) = &choice.icon {
+                                        ui.add(icon.image().fit_to_exact_size(egui::Vec2::splat(
+                                            constants::TITLE_SIZE * 1.5,
+                                        )));
+                                    }
+                                    ui.add(egui::Label::new(
+                                        egui::RichText::new(label)
+                                            .font(FontId::proportional(constants::TITLE_SIZE)),
+                                    ));
+                                });
+
+                                let description = choice.description.trim();
+                                if !description.is_empty() {
+                                    description::draw(ui, description);
+                                    if ui.small_button(t!("description-copy")).clicked() {
+                                        ui.ctx().copy_text(description.to_owned());
+                                    }
+                                }
+                            }
                         })
                 });
                 modal.buttons(ui, |ui| {
-                    if modal.button(ui, "Close").clicked() {
-                        self.winner = None;
+                    if modal.button(ui, t!("winner-close")).clicked() {
+                        event = Some(WinnerEvent::Closed(self.take_winners(wheel_choices)));
                     }
-                    if modal.button(ui, "Remove the winner").clicked() {
-                        if let Some(choice) = &self.winner {
-                            wheel_choices.remove_segment(choice.id, self);
-                            self.winner = None;
-                        }
+                    let remove_text = t!("winner-remove", count = self.winners.len());
+                    if modal.button(ui, remove_text).clicked() {
+                        let winners = self.take_winners(wheel_choices);
+                        let ids: Vec<u32> = winners.iter().map(|choice| choice.id).collect();
+                        wheel_choices.remove_segments(&ids, self);
+                        event = Some(WinnerEvent::Closed(winners));
                     };
+                    let sub_wheel_choice = self
+                        .winners
+                        .iter()
+                        .find(|choice| choice.sub_wheel.is_some())
+                        .cloned();
+                    if let Some(choice) = sub_wheel_choice {
+                        let name = choice.sub_wheel.clone().unwrap_or_default();
+                        if modal
+                            .button(ui, t!("winner-spin-sub-wheel", name = name.as_str()))
+                            .clicked()
+                        {
+                            self.take_winners(wheel_choices);
+                            event = Some(WinnerEvent::OpenSubWheel(choice));
+                        }
+                    }
                 });
             });
         } else {
-            self.rotation += self.spin_velocity;
-            self.spin_velocity *= constants::BREAKING_PERCENT;
             // Stop
-            if self.spin_velocity.abs() < constants::MIN_SPEED {
-                self.spinning = false;
-
-                if let Some(choice) = self.get_winner(&wheel_choices) {
-                    self.winner = Some(choice);
+            let dt = ctx.input(|i| i.stable_dt).min(constants::MAX_FRAME_TIME);
+            if self.step(dt) {
+                let winners = self.finish_spin(wheel_choices);
+                if !winners.is_empty() {
+                    self.winners = winners;
                     modal.open();
                 }
             }
             ctx.request_repaint();
         }
+        event
+    }
+
+    // Fair rotation only lowers the weights of the winners once their dialog
+    // closes, the wheel would otherwise move under the pointer while it shows
+    fn take_winners(&mut self, wheel_choices: &mut WheelChoices) -> Vec<Choice> {
+        let winners = std::mem::take(&mut self.winners);
+        let ids: Vec<u32> = winners.iter().map(|choice| choice.id).collect();
+        wheel_choices.record_wins(&ids);
+        winners
     }
 
-    pub fn start_spin(&mut self) {
+    pub fn start_spin(&mut self, wheel_choices: &WheelChoices) {
+        self.start_spin_with(&mut rand::rng(), wheel_choices);
+    }
+
+    // Spin drawing its randomness from `rng`, a seeded one gives the same spin
+    pub fn start_spin_with(&mut self, rng: &mut impl Rng, wheel_choices: &WheelChoices) {
+        let physics = &wheel_choices.spin_settings.physics;
+        let duration = physics.random_duration(rng);
+        let direction = physics.random_direction(rng);
+        self.launch(rng, duration, direction, None, wheel_choices);
+    }
+
+    // Dragging the wheel rotates it, releasing it fast enough spins it
+    pub fn drag(&mut self, response: &egui::Response, wheel_choices: &WheelChoices) {
+        if self.spinning || !wheel_choices.has_active() {
+            self.drag_samples.clear();
+            return;
+        }
+
+        let time = response.ctx.input(|i| i.time);
+        if response.drag_started() {
+            self.drag_samples.clear();
+        }
+
+        if response.dragged() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let angle = (pos.y - self.center.y).atan2(pos.x - self.center.x);
+                if let Some(&(_, last_angle)) = self.drag_samples.last() {
+                    self.rotation += Wheel::angle_difference(angle, last_angle);
+                    self.wrap_rotation();
+                }
+                self.drag_samples.push((time, angle));
+                self.drag_samples
+                    .retain(|(sample_time, _)| time - sample_time <= constants::FLICK_SAMPLE_TIME);
+            }
+        }
+
+        if response.drag_stopped() {
+            let flick = self.flick_velocity(time);
+            self.drag_samples.clear();
+
+            // Slow releases only move the wheel. Faster ones leave at the
+            // speed of the hand and spin longer, the landing angle stays random
+            // so the result can't be aimed.
+            if flick.abs() >= constants::FLICK_MIN_VELOCITY {
+                let strength = (flick.abs() - constants::FLICK_MIN_VELOCITY)
+                    / (constants::FLICK_MAX_VELOCITY - constants::FLICK_MIN_VELOCITY);
+                let duration = wheel_choices
+                    .spin_settings
+                    .physics
+                    .duration_for_strength(strength);
+                let speed = flick.abs() / constants::FRAME_DURATION;
+                self.launch(
+                    &mut rand::rng(),
+                    duration,
+                    flick.signum(),
+                    Some(speed),
+                    wheel_choices,
+                );
+            }
+        }
+    }
+
+    // Angular velocity of the pointer at release, in radians per frame
+    fn flick_velocity(&self, time: f64) -> f32 {
+        let recent: Vec<&(f64, f32)> = self
+            .drag_samples
+            .iter()
+            .filter(|(sample_time, _)| time - sample_time <= constants::FLICK_SAMPLE_TIME)
+            .collect();
+        let (Some(first), Some(_)) = (recent.first(), recent.get(1)) else {
+            return 0.0;
+        };
+
+        let elapsed = (time - first.0) as f32;
+        if elapsed <= 0.0 {
+            return 0.0;
+        }
+        let angle: f32 = recent
+            .windows(2)
+            .map(|pair| Wheel::angle_difference(pair[1].1, pair[0].1))
+            .sum();
+        angle / elapsed * constants::FRAME_DURATION
+    }
+
+    // Difference between two angles, between -π and π
+    fn angle_difference(angle: f32, previous: f32) -> f32 {
+        (angle - previous + PI).rem_euclid(2.0 * PI) - PI
+    }
+
+    // Spin for `duration` seconds, `direction` is 1 for clockwise and -1 for
+    // counter-clockwise. A flick sets the starting speed in radians per second,
+    // the duration is then adjusted to match it.
+    fn launch(
+        &mut self,
+        rng: &mut impl Rng,
+        duration: f32,
+        direction: f32,
+        flick_speed: Option<f32>,
+        wheel_choices: &WheelChoices,
+    ) {
         if self.spinning {
             return;
         }
-        self.spin_velocity =
-            rand::rng().random_range(constants::SPIN_VELOCITY_MIN..constants::SPIN_VELOCITY_MAX);
+        self.planned_winner = None;
+        self.wrap_rotation();
+        let physics = &wheel_choices.spin_settings.physics;
+
+        // A uniform landing angle gives every choice the odds of its weight
+        let landing_offset = if wheel_choices.spin_settings.winner_first {
+            self.plan_spin(rng, direction, wheel_choices)
+        } else {
+            None
+        }
+        .unwrap_or_else(|| rng.random::<f32>() * 2.0 * PI);
+
+        let (distance, duration) = match flick_speed {
+            Some(speed) => physics.flick(speed, duration, landing_offset),
+            None => (
+                physics.full_turns(duration) * 2.0 * PI + landing_offset,
+                duration,
+            ),
+        };
+        self.spin = Some(Spin::new(
+            self.rotation,
+            direction * distance,
+            duration,
+            physics.curve,
+            physics.bounce,
+        ));
         self.spinning = true;
     }
 
-    pub fn draw(&mut self, painter: &Painter, wheel_choices: &mut WheelChoices) {
-        // Colors
-        let colors = [
-            Color32::from_rgb(51, 105, 232),
-            Color32::from_rgb(213, 15, 37),
-            Color32::from_rgb(238, 178, 17),
-            Color32::from_rgb(0, 153, 37),
-        ];
+    // Advance the spin by `dt` seconds, returns true when the wheel stops
+    pub fn step(&mut self, dt: f32) -> bool {
+        match &mut self.spin {
+            Some(spin) => {
+                self.rotation = spin.advance(dt);
+                if spin.finished() {
+                    self.rotation = spin.end_rotation();
+                    self.wrap_rotation();
+                    self.spin = None;
+                    self.spinning = false;
+                }
+            }
+            None => self.spinning = false,
+        }
+        !self.spinning
+    }
+
+    // Brings `rotation` back between 0 and 2π, keeping count of the turns
+    fn wrap_rotation(&mut self) {
+        let turns = (self.rotation / TAU).floor();
+        self.rotation -= turns * TAU;
+        // Rounding can land exactly on 2π
+        if !(0.0..TAU).contains(&self.rotation) {
+            self.rotation = 0.0;
+        }
+        self.turns += turns as i64;
+    }
+
+    // Find the winner of each pointer
+    pub fn finish_spin(&mut self, wheel_choices: &WheelChoices) -> Vec<Choice> {
+        let planned_winner = self.planned_winner.take();
+        self.get_winners(wheel_choices, planned_winner)
+    }
+
+    // Draw the winner from the weights, then return the part of a turn to add
+    // to the full turns so the wheel stops at a random angle inside its segment
+    fn plan_spin(
+        &mut self,
+        rng: &mut impl Rng,
+        direction: f32,
+        wheel_choices: &WheelChoices,
+    ) -> Option<f32> {
+        let segments = Wheel::get_segments(wheel_choices);
+
+        let mut draw: f64 = rng.random();
+        let winner = segments
+            .iter()
+            .find(|segment| {
+                draw -= segment.probability;
+                draw < 0.0
+            })
+            .or(segments.last())?;
+
+        let landing_angle =
+            winner.start_angle + rng.random::<f32>() * (winner.end_angle - winner.start_angle);
+
+        // The first pointer has to end at `landing_angle` from the start of the
+        // first segment
+        let pointer_angle = wheel_choices.spin_settings.pointer_angles()[0];
+        self.planned_winner = Some(winner.choice.clone());
+        Some((direction * (pointer_angle - landing_angle - self.rotation)).rem_euclid(2.0 * PI))
+    }
+
+    pub fn draw(
+        &mut self,
+        painter: &Painter,
+        wheel_choices: &WheelChoices,
+        highlight: Option<&Highlight>,
+        theme: &Theme,
+    ) {
+        let colors = &theme.palette;
+
+        let segments = Wheel::get_segments(wheel_choices);
 
         // Error message
-        if wheel_choices.choices.is_empty() {
+        if segments.is_empty() {
             painter.text(
                 self.center,
                 Align2::CENTER_CENTER,
-                "Add options to spin the wheel !",
-                FontId::proportional(30.0),
-                Color32::WHITE,
+                if wheel_choices.choices.is_empty() {
+                    t!("wheel-empty")
+                } else if wheel_choices.choices.iter().any(|choice| choice.enabled) {
+                    t!("wheel-no-match")
+                } else {
+                    t!("wheel-all-disabled")
+                },
+                FontId::proportional(
+                    (self.radius / 7.0)
+                        .clamp(constants::MIN_TEXT_SIZE as f32, constants::TITLE_SIZE),
+                ),
+                painter.ctx().style().visuals.strong_text_color(),
             );
             return;
         }
 
-        let total_weight = Wheel::get_total_weight(&wheel_choices);
-
-        let number_of_segments = wheel_choices.choices.len();
-        let angle_step = 2.0 * PI / total_weight as f32;
-
-        let mut last_angle: f32 = self.rotation;
+        let number_of_segments = segments.len();
 
-        for (i, choice) in wheel_choices.choices.iter_mut().enumerate() {
-            let angle_occupied = angle_step * choice.weight as f32;
+        for (i, segment) in segments.into_iter().enumerate() {
             // Start and end angle of the current segment
-            let start_angle: f32 = last_angle;
-            let end_angle = start_angle + angle_occupied;
-
-            last_angle = end_angle;
+            let start_angle: f32 = self.rotation + segment.start_angle;
+            let end_angle: f32 = self.rotation + segment.end_angle;
+            let angle_occupied = end_angle - start_angle;
 
             // Find the color of the segment
             // (Skip a color to prevent 2 from being next to each-other)
             let mut color_index = i;
-            if (number_of_segments % colors.len() == 1) && (i + 1 == number_of_segments) {
+            if colors.len() > 1
+                && (number_of_segments % colors.len() == 1)
+                && (i + 1 == number_of_segments)
+            {
                 color_index += 1;
             }
-            let color: Color32 = colors[color_index % colors.len()];
+            let mut color: Color32 = colors[color_index % colors.len()];
+
+            // Winners stand out, the other segments fade
+            let mut radius = self.radius;
+            let mut is_winner = false;
+            if let Some(highlight) = highlight {
+                if highlight.winners.contains(&segment.choice.id) {
+                    radius *= highlight.scale;
+                    is_winner = true;
+                } else {
+                    color = color.gamma_multiply(1.0 - highlight.dim);
+                }
+            }
 
             // Calculate the points of the segment to draw
             let mut points = vec![self.center];
 
             let mut side_points: (Point, Point) = (Point::new(), Point::new());
 
-            let actual_steps: u8 =
-                ((constants::STEPS / total_weight as u8) * choice.weight as u8) as u8;
+            // Thin segments need fewer points than wide ones, a sliver is a triangle
+            let actual_steps: usize = (angle_occupied / (2.0 * PI) * constants::STEPS as f32)
+                .min(radius * angle_occupied / constants::MIN_STEP_LENGTH)
+                .ceil()
+                .max(1.0) as usize;
 
-            for j in 0..=actual_steps as u8 {
+            for j in 0..=actual_steps {
                 let t: f32 = j as f32 / actual_steps as f32;
                 let angle: f32 = start_angle + t * (end_angle - start_angle);
-                let x: f32 = self.center.x + self.radius * angle.cos();
-                let y: f32 = self.center.y + self.radius * angle.sin();
+                let x: f32 = self.center.x + radius * angle.cos();
+                let y: f32 = self.center.y + radius * angle.sin();
                 let pos = egui::pos2(x, y);
                 points.push(egui::pos2(x, y));
                 if j == 0 {
@@ -166,90 +490,370 @@ impl Wheel {
             points.push(self.center);
 
             // Draw the segment
-            let path = PathShape::convex_polygon(
-                points,
-                color,
-                Stroke::NONE, // Stroke::new(2 as f32, Color32::from_rgb(0, 0, 0)),
-            );
+            let stroke = if is_winner {
+                Stroke::new(constants::WINNER_OUTLINE, theme.label_color)
+            } else {
+                Stroke::NONE
+            };
+            let path = PathShape::convex_polygon(points, color, stroke);
             painter.add(path);
 
-            // Draw the text
+            // Labels and pictures of segments too thin to read are left out
+            if number_of_segments > 1
+                && angle_occupied < PI
+                && segment_width < constants::MIN_TEXT_SIZE as f32
+            {
+                continue;
+            }
+
+            // Draw the picture, the label makes room for it
             let text_angle: f32 = start_angle + angle_occupied / 2.0;
+            let mut text_radius = self.radius * 0.6;
+            let texture = segment
+                .choice
+                .icon
+                .as_ref()
+                .and_then(|icon| icon.texture(painter.ctx()));
+            if let Some(texture) = texture {
+                let tint = match highlight {
+                    Some(highlight) if !is_winner => {
+                        Color32::WHITE.gamma_multiply(1.0 - highlight.dim)
+                    }
+                    _ => Color32::WHITE,
+                };
+                let (distance, max_size) = if segment.choice.show_label {
+                    (self.radius * 0.78, self.radius * 0.32)
+                } else {
+                    (self.radius * 0.6, self.radius * 0.5)
+                };
+                self.draw_icon(
+                    painter,
+                    texture,
+                    text_angle,
+                    angle_occupied,
+                    distance,
+                    max_size,
+                    tint,
+                );
+                if !segment.choice.show_label {
+                    continue;
+                }
+                text_radius = self.radius * 0.42;
+            }
+
+            // Draw the text
             painter.add(Wheel::create_text_shape(
-                choice.label.to_owned(),
+                segment.choice.label.to_owned(),
                 painter,
                 text_angle,
-                self.radius,
+                text_radius,
                 self.center,
                 segment_width,
+                theme,
             ));
         }
     }
 
-    pub fn get_triangle_center(&self) -> Pos2 {
-        egui::pos2(self.center.x + self.radius, self.center.y)
+    // Picture standing upright towards the rim, as large as the segment allows
+    #[allow(clippy::too_many_arguments)]
+    fn draw_icon(
+        &self,
+        painter: &Painter,
+        texture: SizedTexture,
+        angle: f32,
+        angle_occupied: f32,
+        distance: f32,
+        max_size: f32,
+        tint: Color32,
+    ) {
+        // Largest square centered at `distance` that stays inside the segment
+        let half_sin = (angle_occupied.min(PI) / 2.0).sin();
+        let size = (2.0 * distance * half_sin / (1.0 + half_sin)).min(max_size);
+        if size < constants::MIN_ICON_SIZE {
+            return;
+        }
+
+        let center = egui::pos2(
+            self.center.x + distance * angle.cos(),
+            self.center.y + distance * angle.sin(),
+        );
+        let scale = size / texture.size.max_elem().max(1.0);
+        let mut mesh = Mesh::with_texture(texture.id);
+        mesh.add_rect_with_uv(
+            Rect::from_center_size(center, texture.size * scale),
+            Rect::from_min_max(Pos2::ZERO, egui::pos2(1.0, 1.0)),
+            tint,
+        );
+        mesh.rotate(Rot2::from_angle(angle + PI / 2.0), center);
+        painter.add(mesh);
+    }
+
+    pub fn draw_pointers(&self, painter: &Painter, spin_settings: &SpinSettings, theme: &Theme) {
+        // Pointers shrink with small wheels
+        let scale = (self.radius / constants::POINTER_REFERENCE_RADIUS).clamp(0.5, 1.0);
+        for angle in spin_settings.pointer_angles() {
+            let (sin, cos) = angle.sin_cos();
+            let rotate = |along: f32, across: f32| {
+                egui::pos2(
+                    self.center.x + along * cos - across * sin,
+                    self.center.y + along * sin + across * cos,
+                )
+            };
+
+            let triangle_points: Vec<Pos2> = vec![
+                rotate(self.radius - 15.0 * scale, 0.0),
+                rotate(self.radius + 30.0 * scale, 20.0 * scale),
+                rotate(self.radius + 30.0 * scale, -20.0 * scale),
+            ];
+            let path = PathShape::convex_polygon(triangle_points, theme.pointer, Stroke::NONE);
+            painter.add(path);
+        }
+    }
+
+    // Screen readers see the wheel as the list of its segments
+    pub fn describe(&self, ctx: &Context, id: egui::Id, wheel_choices: &WheelChoices) {
+        let segments = Wheel::get_segments(wheel_choices);
+        ctx.accesskit_node_builder(id, |node| {
+            node.set_role(Role::List);
+            node.set_label(t!("wheel-name"));
+            node.set_size_of_set(segments.len());
+        });
+
+        ctx.with_accessibility_parent(id, || {
+            for (i, segment) in segments.iter().enumerate() {
+                // Around the label of the segment
+                let angle = self.rotation + (segment.start_angle + segment.end_angle) / 2.0;
+                let label_center = self.center + egui::Vec2::angled(angle) * self.radius * 0.6;
+                let half_size = (self.radius * 0.1) as f64;
+                ctx.accesskit_node_builder(id.with(segment.choice.id), |node| {
+                    node.set_role(Role::ListItem);
+                    node.set_label(t!(
+                        "wheel-segment",
+                        name = segment.choice.label.as_str(),
+                        percent = format!("{:.1}", segment.probability * 100.0)
+                    ));
+                    node.set_position_in_set(i + 1);
+                    node.set_size_of_set(segments.len());
+                    node.set_bounds(accesskit::Rect {
+                        x0: label_center.x as f64 - half_size,
+                        y0: label_center.y as f64 - half_size,
+                        x1: label_center.x as f64 + half_size,
+                        y1: label_center.y as f64 + half_size,
+                    });
+                });
+            }
+        });
     }
 
-    pub fn reset_rotation(&mut self, choices: &Vec<Choice>) {
-        self.rotation = PI / choices.len() as f32
+    // Held by the mouse or a finger
+    pub fn dragging(&self) -> bool {
+        !self.drag_samples.is_empty()
     }
 
-    fn get_winner(&self, wheel_choices: &WheelChoices) -> Option<Choice> {
-        if self.spinning {
+    pub fn winners(&self) -> &[Choice] {
+        &self.winners
+    }
+
+    #[cfg(test)]
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    // Rotation with the full turns, for measuring how far the wheel moved
+    pub fn total_rotation(&self) -> f64 {
+        self.rotation as f64 + self.turns as f64 * std::f64::consts::TAU
+    }
+
+    // Whether a segment boundary passed under a pointer since the wheel was
+    // at `previous_rotation`
+    pub fn boundary_crossed(&self, previous_rotation: f64, wheel_choices: &WheelChoices) -> bool {
+        let rotation = self.total_rotation();
+        if rotation == previous_rotation || !rotation.is_finite() || !previous_rotation.is_finite()
+        {
+            return false;
+        }
+        let segments = Wheel::get_segments(wheel_choices);
+        if segments.is_empty() {
+            return false;
+        }
+        wheel_choices
+            .spin_settings
+            .pointer_angles()
+            .into_iter()
+            .any(|angle| {
+                Wheel::boundary_count(angle, previous_rotation, &segments)
+                    != Wheel::boundary_count(angle, rotation, &segments)
+            })
+    }
+
+    // Boundaries between a fixed origin and the pointer at `angle`, two counts
+    // differ when the wheel turned past at least one boundary
+    fn boundary_count(angle: f32, rotation: f64, segments: &[Segment]) -> i64 {
+        let relative_angle = angle as f64 - rotation;
+        let turns = (relative_angle / std::f64::consts::TAU).floor() as i64;
+        let wrapped = relative_angle.rem_euclid(std::f64::consts::TAU) as f32;
+        let index = segments
+            .iter()
+            .position(|segment| wrapped < segment.end_angle)
+            .unwrap_or(segments.len() - 1);
+        turns * segments.len() as i64 + index as i64
+    }
+
+    pub fn reset_rotation(&mut self, choices: &[Choice]) {
+        self.rotation = if choices.is_empty() {
+            0.0
+        } else {
+            PI / choices.len() as f32
+        };
+    }
+
+    // Choice under the given point, with its probability of winning
+    pub fn choice_at(&self, pos: Pos2, wheel_choices: &WheelChoices) -> Option<(Choice, f64)> {
+        if self.center.distance(pos) > self.radius {
             return None;
         }
+        let angle = (pos.y - self.center.y).atan2(pos.x - self.center.x);
+        let segments = Wheel::get_segments(wheel_choices);
+        self.segment_at_angle(angle, &segments)
+            .map(|index| (segments[index].choice.clone(), segments[index].probability))
+    }
 
-        let angle_step = 2.0 * PI / Wheel::get_total_weight(&wheel_choices) as f32;
-        let mut last_angle: f32 = self.rotation;
-        let mut minimum: Option<(Choice, f32)> = None;
+    // Winner of each pointer, the first one can be drawn before spinning
+    fn get_winners(
+        &self,
+        wheel_choices: &WheelChoices,
+        planned_winner: Option<Choice>,
+    ) -> Vec<Choice> {
+        if self.spinning {
+            return Vec::new();
+        }
+
+        let segments = Wheel::get_segments(wheel_choices);
+        let mut winners: Vec<usize> = Vec::new();
 
-        for choice in wheel_choices.choices.iter() {
-            let end_angle: f32 = last_angle + angle_step * choice.weight as f32;
-            let actual_end_angle = end_angle % (2.0 * PI);
+        for (i, pointer_angle) in wheel_choices
+            .spin_settings
+            .pointer_angles()
+            .into_iter()
+            .enumerate()
+        {
+            let planned_index = planned_winner
+                .as_ref()
+                .filter(|_| i == 0)
+                .and_then(|planned| {
+                    segments
+                        .iter()
+                        .position(|segment| segment.choice.id == planned.id)
+                });
+            let Some(mut index) =
+                planned_index.or_else(|| self.segment_at_angle(pointer_angle, &segments))
+            else {
+                continue;
+            };
 
-            if minimum.is_none() || actual_end_angle < minimum.clone().unwrap().1 {
-                minimum = Some((choice.clone(), actual_end_angle));
+            // Give the pointer the next choice that didn't win yet
+            if wheel_choices.spin_settings.duplicates == DuplicatePolicy::NextChoice
+                && winners.len() < segments.len()
+            {
+                while winners.contains(&index) {
+                    index = (index + 1) % segments.len();
+                }
             }
+            winners.push(index);
+        }
 
-            last_angle = end_angle;
+        winners
+            .into_iter()
+            .map(|index| segments[index].choice.clone())
+            .collect()
+    }
+
+    fn segment_at_angle(&self, angle: f32, segments: &[Segment]) -> Option<usize> {
+        if segments.is_empty() {
+            return None;
         }
 
-        match minimum {
-            None => None,
-            Some(choice) => Some(choice.0),
+        // Angle relative to the start of the first segment
+        let relative_angle = (angle - self.rotation).rem_euclid(2.0 * PI);
+
+        segments
+            .iter()
+            .position(|segment| relative_angle < segment.end_angle)
+            // Rounding errors can leave the angle past the last segment
+            .or(Some(segments.len() - 1))
+    }
+
+    // Angles occupied by the enabled choices, in the same proportions as their
+    // weights. Both the drawing and the winner detection use them.
+    pub fn get_segments(wheel_choices: &WheelChoices) -> Vec<Segment<'_>> {
+        let weighted_choices = wheel_choices.weighted_choices();
+        let total_weight: f64 = weighted_choices.iter().map(|(_, weight)| weight).sum();
+
+        let mut segments = Vec::with_capacity(weighted_choices.len());
+        let mut cumulated_weight: f64 = 0.0;
+        for (choice, weight) in weighted_choices {
+            let start_angle = (2.0 * std::f64::consts::PI * cumulated_weight / total_weight) as f32;
+            cumulated_weight += weight;
+            let end_angle = (2.0 * std::f64::consts::PI * cumulated_weight / total_weight) as f32;
+
+            segments.push(Segment {
+                choice,
+                probability: weight / total_weight,
+                start_angle,
+                end_angle,
+            });
         }
+        segments
     }
 
     fn create_text_shape(
         text: String,
         painter: &Painter,
         text_angle: f32,
-        wheel_radius: f32,
+        text_radius: f32,
         text_center: Pos2,
         segment_width: f32,
+        theme: &Theme,
     ) -> TextShape {
-        let actual_label: String = if text.len() > constants::MAX_RANGE_TEXT_LENGTH {
-            format!("{}..", text[..constants::MAX_RANGE_TEXT_LENGTH].to_string())
+        // Cut on characters, emoji and accents take several bytes
+        let actual_label: String = if text.chars().count() > constants::MAX_RANGE_TEXT_LENGTH {
+            let start: String = text
+                .chars()
+                .take(constants::MAX_RANGE_TEXT_LENGTH)
+                .collect();
+            format!("{start}..")
         } else {
             text
         };
 
-        let text_radius: f32 = wheel_radius * 0.6;
+        // A lone segment has no edges to fit between
         let real_width = if segment_width < 1.0 {
-            wheel_radius
+            text_radius * 1.5
         } else {
             segment_width
         };
 
+        // Start from the size a linear scaling gives, then shrink the last points
+        let font = |size: usize| FontId::new(size as f32, theme.label_font.family());
+        let largest = painter
+            .layout_no_wrap(
+                actual_label.clone(),
+                font(constants::MAX_TEXT_SIZE),
+                theme.label_color,
+            )
+            .size();
+        let fit = (text_radius / largest.x).min(real_width * 0.9 / largest.y);
+        let mut current_text_size: usize = ((constants::MAX_TEXT_SIZE as f32 * fit) as usize)
+            .clamp(constants::MIN_TEXT_SIZE, constants::MAX_TEXT_SIZE);
+
         let mut galley;
-        let mut current_text_size: usize = constants::MAX_TEXT_SIZE;
         let mut text_size;
         loop {
             galley = {
                 painter.layout_no_wrap(
                     actual_label.clone(),
-                    FontId::proportional(current_text_size as f32),
-                    Color32::WHITE,
+                    font(current_text_size),
+                    theme.label_color,
                 )
             };
             text_size = galley.size();
@@ -258,7 +862,7 @@ impl Wheel {
             {
                 break;
             }
-            current_text_size = current_text_size - 1;
+            current_text_size -= 1;
         }
 
         let text_center = Pos2::new(
@@ -281,16 +885,95 @@ impl Wheel {
 
         TextShape {
             angle: text_angle,
-            ..TextShape::new(centered_point, galley, Color32::WHITE)
+            ..TextShape::new(centered_point, galley, theme.label_color)
+        }
+    }
+}
+
+impl Default for SpinSettings {
+    fn default() -> Self {
+        Self {
+            winner_first: false,
+            pointers: vec![0.0],
+            duplicates: DuplicatePolicy::Allow,
+            physics: SpinPhysics::default(),
+        }
+    }
+}
+
+impl SpinSettings {
+    // Pointer angles in radians, there is always at least one pointer
+    pub fn pointer_angles(&self) -> Vec<f32> {
+        if self.pointers.is_empty() {
+            return vec![0.0];
         }
+        self.pointers
+            .iter()
+            .map(|angle| angle.to_radians())
+            .collect()
+    }
+
+    pub fn draw(&mut self, ui: &mut egui::Ui, enabled: bool) {
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.checkbox(&mut self.winner_first, t!("winner-first"))
+                .on_hover_text(t!("winner-first-tooltip"));
+
+            ui.label(t!("pointers-label"));
+            let mut pointer_to_remove: Option<usize> = None;
+            let can_remove = self.pointers.len() > 1;
+            ui.horizontal_wrapped(|ui| {
+                for (i, angle) in self.pointers.iter_mut().enumerate() {
+                    ui.add(egui::DragValue::new(angle).range(0.0..=359.0).suffix("°"));
+                    let remove_response =
+                        ui.add_enabled(can_remove, egui::Button::new("🗑").small());
+                    accessibility::set_name(&remove_response, t!("pointer-remove", number = i + 1));
+                    if remove_response.clicked() {
+                        pointer_to_remove = Some(i);
+                    }
+                }
+            });
+            if let Some(i) = pointer_to_remove {
+                self.pointers.remove(i);
+            }
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        self.pointers.len() < constants::MAX_POINTERS,
+                        egui::Button::new(t!("pointer-add")),
+                    )
+                    .clicked()
+                {
+                    self.pointers.push(0.0);
+                }
+                if ui.button(t!("pointers-spread")).clicked() {
+                    let count = self.pointers.len().max(1);
+                    self.pointers = (0..count)
+                        .map(|i| i as f32 * 360.0 / count as f32)
+                        .collect();
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t!("duplicates-label"));
+                egui::ComboBox::from_id_salt("duplicate_policy")
+                    .selected_text(self.duplicates.label())
+                    .show_ui(ui, |ui| {
+                        for policy in [DuplicatePolicy::Allow, DuplicatePolicy::NextChoice] {
+                            ui.selectable_value(&mut self.duplicates, policy, policy.label());
+                        }
+                    });
+            });
+        });
     }
+}
 
-    fn get_total_weight(choices: &WheelChoices) -> u32 {
-        let mut total: u32 = 0;
-        for choice in choices.choices.iter() {
-            total += choice.weight;
+impl DuplicatePolicy {
+    fn label(&self) -> String {
+        match self {
+            DuplicatePolicy::Allow => t!("duplicates-allow"),
+            DuplicatePolicy::NextChoice => t!("duplicates-next-choice"),
         }
-        total
     }
 }
 
@@ -304,6 +987,220 @@ impl Point {
     }
 
     fn distance_to(&self, other: &Point) -> f32 {
-        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt() as f32
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+    use egui::{epaint::Shape, Pos2};
+
+    use super::{DuplicatePolicy, Segment, Wheel};
+    use crate::app::{
+        constants,
+        theme::{Appearance, ThemeSettings},
+        Choice, WheelChoices,
+    };
+
+    // Wheel with one choice per weight, labelled "A", "B", ...
+    fn weighted_wheel(weights: &[f64]) -> (WheelChoices, Wheel) {
+        let mut wheel_choices = WheelChoices::new();
+        let mut wheel = Wheel::new();
+        for (index, weight) in weights.iter().enumerate() {
+            let label = char::from(b'A' + index as u8).to_string();
+            wheel_choices.add_segment(label, &mut wheel);
+            let id = wheel_choices.choices[index].id;
+            wheel_choices.set_weight(id, *weight);
+        }
+        wheel.rotation = 0.0;
+        (wheel_choices, wheel)
+    }
+
+    fn labels(segments: &[Segment]) -> Vec<String> {
+        segments
+            .iter()
+            .map(|segment| segment.choice.label.clone())
+            .collect()
+    }
+
+    fn labels_of(choices: &[Choice]) -> Vec<String> {
+        choices.iter().map(|choice| choice.label.clone()).collect()
+    }
+
+    #[test]
+    fn segments_follow_the_weights() {
+        let (wheel_choices, _) = weighted_wheel(&[1.0, 1.0, 2.0]);
+        let segments = Wheel::get_segments(&wheel_choices);
+
+        let probabilities: Vec<f64> = segments.iter().map(|segment| segment.probability).collect();
+        assert_eq!(probabilities, [0.25, 0.25, 0.5]);
+        assert_eq!(segments[0].start_angle, 0.0);
+        assert_eq!(segments[0].end_angle, FRAC_PI_2);
+        assert_eq!(segments[1].end_angle, PI);
+        assert_eq!(segments[2].end_angle, TAU);
+        for pair in segments.windows(2) {
+            assert_eq!(pair[0].end_angle, pair[1].start_angle);
+        }
+    }
+
+    #[test]
+    fn arcs_match_the_probabilities() {
+        let min = constants::MIN_SEGMENT_WEIGHT;
+        let max = constants::MAX_SEGMENT_WEIGHT;
+        for weights in [
+            vec![0.5, 1.5, 2.25],
+            vec![min, 0.5, 1e9, max],
+            vec![max, min, 1e9, 0.5],
+            vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7],
+            vec![min, min, min],
+        ] {
+            let (wheel_choices, _) = weighted_wheel(&weights);
+            let segments = Wheel::get_segments(&wheel_choices);
+            let total: f64 = weights.iter().sum();
+
+            assert_eq!(segments.len(), weights.len());
+            assert_eq!(segments[0].start_angle, 0.0);
+            assert_eq!(segments.last().unwrap().end_angle, TAU);
+            for (segment, weight) in segments.iter().zip(&weights) {
+                let expected = std::f64::consts::TAU * weight / total;
+                let arc = (segment.end_angle - segment.start_angle) as f64;
+                // Angles are f32, a few steps of their precision near 2π
+                assert!(
+                    (arc - expected).abs() < 1e-6,
+                    "{weights:?}: arc {arc} for weight {weight}"
+                );
+                assert!((segment.probability - weight / total).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn segments_leave_out_disabled_choices() {
+        let (mut wheel_choices, _) = weighted_wheel(&[1.0, 1.0, 1.0]);
+        let id = wheel_choices.choices[1].id;
+        wheel_choices.set_enabled(id, false);
+
+        let segments = Wheel::get_segments(&wheel_choices);
+        assert_eq!(labels(&segments), ["A", "C"]);
+        assert_eq!(segments[0].probability, 0.5);
+
+        wheel_choices.enable_all();
+        let segments = Wheel::get_segments(&wheel_choices);
+        assert_eq!(labels(&segments), ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn segments_leave_out_filtered_choices() {
+        let (mut wheel_choices, _) = weighted_wheel(&[1.0, 1.0, 1.0, 1.0]);
+        let id = wheel_choices.choices[1].id;
+        wheel_choices.set_enabled(id, false);
+        wheel_choices.choices[1].tags = vec!["Red".to_owned()];
+        wheel_choices.choices[2].tags = vec!["Red".to_owned()];
+        wheel_choices.choices[3].tags = vec!["Blue".to_owned()];
+        wheel_choices.tag_filter.tags = vec!["red".to_owned()];
+
+        // A disabled choice stays out even when its tags match
+        let segments = Wheel::get_segments(&wheel_choices);
+        assert_eq!(labels(&segments), ["C"]);
+        assert_eq!(segments[0].probability, 1.0);
+
+        wheel_choices.tag_filter.tags.clear();
+        let segments = Wheel::get_segments(&wheel_choices);
+        assert_eq!(labels(&segments), ["A", "C", "D"]);
+    }
+
+    #[test]
+    fn weights_keep_a_minimum() {
+        let (wheel_choices, _) = weighted_wheel(&[0.0, -5.0, 1.0]);
+        let segments = Wheel::get_segments(&wheel_choices);
+
+        assert_eq!(labels(&segments), ["A", "B", "C"]);
+        for segment in &segments[..2] {
+            assert_eq!(segment.choice.weight, constants::MIN_SEGMENT_WEIGHT);
+            assert!(segment.probability > 0.0);
+            assert!(segment.end_angle > segment.start_angle);
+        }
+    }
+
+    #[test]
+    fn winner_at_segment_boundaries() {
+        let (wheel_choices, wheel) = weighted_wheel(&[1.0, 1.0, 2.0]);
+        let segments = Wheel::get_segments(&wheel_choices);
+        let at = |angle: f32| wheel.segment_at_angle(angle, &segments);
+
+        assert_eq!(at(0.0), Some(0));
+        // A boundary belongs to the segment starting there
+        assert_eq!(at(segments[1].start_angle.next_down()), Some(0));
+        assert_eq!(at(segments[1].start_angle), Some(1));
+        assert_eq!(at(segments[2].start_angle), Some(2));
+        assert_eq!(at(TAU.next_down()), Some(2));
+        assert_eq!(at(TAU), Some(0));
+        assert_eq!(at(-0.1), Some(2));
+        assert_eq!(wheel.segment_at_angle(0.0, &[]), None);
+    }
+
+    #[test]
+    fn winners_of_each_pointer() {
+        let (mut wheel_choices, mut wheel) = weighted_wheel(&[1.0, 1.0, 2.0]);
+        wheel_choices.spin_settings.pointers = vec![45.0, 135.0, 300.0];
+        assert_eq!(
+            labels_of(&wheel.get_winners(&wheel_choices, None)),
+            ["A", "B", "C"]
+        );
+
+        // Turning the wheel by a quarter brings the previous segments under the pointers
+        wheel.rotation = FRAC_PI_2;
+        assert_eq!(
+            labels_of(&wheel.get_winners(&wheel_choices, None)),
+            ["C", "A", "C"]
+        );
+
+        wheel_choices.spin_settings.duplicates = DuplicatePolicy::NextChoice;
+        assert_eq!(
+            labels_of(&wheel.get_winners(&wheel_choices, None)),
+            ["C", "A", "B"]
+        );
+    }
+
+    #[test]
+    fn large_wheel_stays_light() {
+        let mut wheel_choices = WheelChoices::new();
+        let mut wheel = Wheel::new();
+        for i in 0..5000 {
+            let choice = wheel_choices.create_choice(format!("Choice {i}"));
+            wheel_choices.choices.push(choice);
+        }
+        wheel.center = Pos2::new(400.0, 400.0);
+        wheel.radius = 300.0;
+
+        let segments = Wheel::get_segments(&wheel_choices);
+        assert_eq!(segments.len(), 5000);
+        assert_eq!(segments.last().unwrap().end_angle, TAU);
+
+        let appearance = Appearance::new(ThemeSettings::default());
+        let ctx = egui::Context::default();
+        let output = ctx.run(Default::default(), |ctx| {
+            let painter = ctx.layer_painter(egui::LayerId::background());
+            wheel.draw(&painter, &wheel_choices, None, appearance.theme());
+        });
+
+        let mut paths = 0;
+        let mut points = 0;
+        for clipped in &output.shapes {
+            match &clipped.shape {
+                Shape::Path(path) => {
+                    paths += 1;
+                    points += path.points.len();
+                }
+                // Labels of such thin segments can't be read
+                Shape::Text(_) => panic!("label drawn in a thin segment"),
+                _ => {}
+            }
+        }
+        assert_eq!(paths, 5000);
+        // A thin segment is a triangle, the center and two points on the rim
+        assert!(points <= 5000 * 4, "{points} points");
     }
 }
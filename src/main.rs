@@ -1,12 +1,29 @@
-#![windows_subsystem = "windows"]
+#![cfg_attr(not(target_arch = "wasm32"), windows_subsystem = "windows")]
 mod app;
 
 use crate::app::App;
-use eframe::egui::{self, Vec2};
 
+#[cfg(not(target_arch = "wasm32"))]
 const APP_TITLE: &str = "CargoSpin";
 
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
+    use eframe::egui::{self, Vec2};
+
+    // Headless fairness check: `cargo_spin simulate A B:2 ...`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|command| command == "simulate") {
+        attach_console();
+        match app::run_simulation_cli(&args[1..]) {
+            Ok(report) => println!("{report}"),
+            Err(error) => {
+                eprintln!("{error}");
+                std::process::exit(2);
+            }
+        }
+        return Ok(());
+    }
+
     // Options
     let window_size = Vec2 {
         x: 1200.0,
@@ -16,7 +33,6 @@ fn main() -> eframe::Result<()> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size(window_size)
-            .with_min_inner_size(window_size)
             .with_icon(
                 eframe::icon_data::from_png_bytes(&include_bytes!("../assets/icon.png")[..])
                     .expect("Failed to load icon"),
@@ -27,6 +43,60 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         APP_TITLE,
         options,
-        Box::new(|_cc| Ok(Box::new(App::default()))),
+        Box::new(|cc| Ok(Box::new(App::new(cc)))),
     )
 }
+
+// The app has no console of its own on Windows, print in the terminal that
+// started it if there is one
+#[cfg(windows)]
+fn attach_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // SAFETY: only asks Windows for the console of the parent process, a
+    // failure leaves the output discarded as before
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(all(not(windows), not(target_arch = "wasm32")))]
+fn attach_console() {}
+
+// In the browser the app state is persisted in local storage
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use eframe::wasm_bindgen::JsCast;
+
+    wasm_bindgen_futures::spawn_local(async {
+        let document = eframe::web_sys::window()
+            .expect("No window")
+            .document()
+            .expect("No document");
+        let canvas = document
+            .get_element_by_id("the_canvas_id")
+            .expect("Failed to find the_canvas_id")
+            .dyn_into::<eframe::web_sys::HtmlCanvasElement>()
+            .expect("the_canvas_id was not a HtmlCanvasElement");
+
+        let start_result = eframe::WebRunner::new()
+            .start(
+                canvas,
+                eframe::WebOptions::default(),
+                Box::new(|cc| Ok(Box::new(App::new(cc)))),
+            )
+            .await;
+
+        // Replace the loading text with the app, or with the error
+        if let Some(loading_text) = document.get_element_by_id("loading_text") {
+            match start_result {
+                Ok(_) => loading_text.remove(),
+                Err(error) => {
+                    loading_text
+                        .set_inner_html("<p>The app has crashed, see the console for details.</p>");
+                    panic!("Failed to start eframe: {error:?}");
+                }
+            }
+        }
+    });
+}